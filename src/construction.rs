@@ -0,0 +1,310 @@
+use serde::Serialize;
+
+use crate::{
+    lattice::Lattice,
+    word::{Aspect, Conjugation, Detail, Mood, PartOfSpeech, Polarity, Tense, Word},
+};
+
+/// verbal construction in a lattice
+///
+/// A construction is either a single verb with a negative suffix, e.g. "genehekū",
+/// or a main verb followed by an auxiliary, e.g. "genehe akū" or "tuwame bi".
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Construction {
+    /// index of the first token of the construction
+    pub start: usize,
+    /// number of tokens in the construction
+    pub len: usize,
+    pub polarity: Polarity,
+    pub aspect: Aspect,
+    pub tense: Tense,
+}
+
+/// quotation or purpose clause closed by a form of "se"
+///
+/// For example, in "cooha be waki seme", "seme" is the quotative
+/// and "waki" is the finite verb ending the quoted clause.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Quotation {
+    /// index of the quotative in the minimum cost path
+    pub quotative: usize,
+    /// index of the finite verb just before the quotative in the minimum cost path
+    ///
+    /// If it is `None`, the quoted clause does not end with a finite verb.
+    pub verb: Option<usize>,
+}
+
+/// rule of an analytic construction consisting of a main verb and an auxiliary
+struct ConstructionRule {
+    /// conjugation of the main verb
+    conjugation: Conjugation,
+    /// stem of the auxiliary
+    auxiliary: &'static str,
+    /// conjugation of the auxiliary
+    ///
+    /// If it is `None`, the auxiliary is not conjugated, e.g. "akū".
+    auxiliary_conjugation: Option<Conjugation>,
+    polarity: Polarity,
+    aspect: Aspect,
+    tense: Tense,
+}
+
+const AKU: &str = "akū";
+
+const CONSTRUCTION_RULES: [ConstructionRule; 6] = [
+    // "genehe akū"
+    ConstructionRule {
+        conjugation: Conjugation::PerfectiveParticiple,
+        auxiliary: AKU,
+        auxiliary_conjugation: None,
+        polarity: Polarity::Negative,
+        aspect: Aspect::Perfective,
+        tense: Tense::Past,
+    },
+    // "genere akū"
+    ConstructionRule {
+        conjugation: Conjugation::ProspectiveFinite,
+        auxiliary: AKU,
+        auxiliary_conjugation: None,
+        polarity: Polarity::Negative,
+        aspect: Aspect::Imperfective,
+        tense: Tense::NonPast,
+    },
+    // "tuwame bi"
+    ConstructionRule {
+        conjugation: Conjugation::ImperfectiveConverb,
+        auxiliary: "bi",
+        auxiliary_conjugation: Some(Conjugation::ImperfectiveFinite),
+        polarity: Polarity::Positive,
+        aspect: Aspect::Progressive,
+        tense: Tense::NonPast,
+    },
+    // "tuwame bihe"
+    ConstructionRule {
+        conjugation: Conjugation::ImperfectiveConverb,
+        auxiliary: "bi",
+        auxiliary_conjugation: Some(Conjugation::PerfectiveParticiple),
+        polarity: Polarity::Positive,
+        aspect: Aspect::Progressive,
+        tense: Tense::Past,
+    },
+    // "genehe bihe"
+    ConstructionRule {
+        conjugation: Conjugation::PerfectiveParticiple,
+        auxiliary: "bi",
+        auxiliary_conjugation: Some(Conjugation::PerfectiveParticiple),
+        polarity: Polarity::Positive,
+        aspect: Aspect::Perfective,
+        tense: Tense::Past,
+    },
+    // "tuwambihe bi"
+    ConstructionRule {
+        conjugation: Conjugation::PerfectiveProcessiveParticiple,
+        auxiliary: "bi",
+        auxiliary_conjugation: Some(Conjugation::ImperfectiveFinite),
+        polarity: Polarity::Positive,
+        aspect: Aspect::Imperfective,
+        tense: Tense::Past,
+    },
+];
+
+impl ConstructionRule {
+    fn matches(&self, main: &Word, auxiliary: &Word) -> bool {
+        if conjugation_of(main) != Some(self.conjugation) || auxiliary.base != self.auxiliary {
+            return false;
+        }
+        match self.auxiliary_conjugation {
+            Some(conjugation) => conjugation_of(auxiliary) == Some(conjugation),
+            None => auxiliary.suffixes.is_none(),
+        }
+    }
+}
+
+fn conjugation_of(word: &Word) -> Option<Conjugation> {
+    match word.detail {
+        Some(Detail::Conjugation(conjugation)) => Some(conjugation),
+        _ => None,
+    }
+}
+
+/// Returns the features of a single verb with a negative suffix.
+fn negative_features(conjugation: Conjugation) -> Option<(Aspect, Tense)> {
+    match conjugation {
+        Conjugation::NegativePerfectiveFinite | Conjugation::NegativePerfectiveConverb => {
+            Some((Aspect::Perfective, Tense::Past))
+        }
+        Conjugation::NegativeImperfectiveFinite => Some((Aspect::Imperfective, Tense::NonPast)),
+        _ => None,
+    }
+}
+
+/// Find negative and auxiliary constructions in the minimum cost path of a lattice.
+///
+/// An analytic construction takes precedence over a single verb in it.
+/// A node spanning several tokens, e.g. a multi-token function word, is never part of a construction.
+/// The path costs of the lattice must be calculated in advance.
+pub fn analyze_constructions(lattice: &Lattice) -> Vec<Construction> {
+    let path: Vec<(usize, Word)> = lattice
+        .get_min_cost_path_with_spans()
+        .into_iter()
+        .filter(|(_, span, _)| *span == 1)
+        .filter_map(|(start, _, words)| words.into_iter().next().map(|word| (start, word)))
+        .collect();
+    let mut constructions = vec![];
+    let mut index = 0;
+    while index < path.len() {
+        let (start, main) = &path[index];
+        let rule = path
+            .get(index + 1)
+            .filter(|(next_start, _)| *next_start == start + 1)
+            .and_then(|(_, auxiliary)| {
+                CONSTRUCTION_RULES
+                    .iter()
+                    .find(|rule| rule.matches(main, auxiliary))
+            });
+        if let Some(rule) = rule {
+            constructions.push(Construction {
+                start: *start,
+                len: 2,
+                polarity: rule.polarity,
+                aspect: rule.aspect,
+                tense: rule.tense,
+            });
+            index += 2;
+            continue;
+        }
+        if let Some((aspect, tense)) = conjugation_of(main).and_then(negative_features) {
+            constructions.push(Construction {
+                start: *start,
+                len: 1,
+                polarity: Polarity::Negative,
+                aspect,
+                tense,
+            });
+        }
+        index += 1;
+    }
+    constructions
+}
+
+/// Find quotatives in the minimum cost path of a lattice.
+///
+/// The path costs of the lattice must be calculated in advance.
+pub fn find_quotations(lattice: &Lattice) -> Vec<Quotation> {
+    let path = lattice.get_min_cost_path();
+    path.iter()
+        .enumerate()
+        .filter(|(_, words)| {
+            words
+                .first()
+                .is_some_and(|word| word.part_of_speech == PartOfSpeech::Quotative)
+        })
+        .map(|(index, _)| {
+            let verb = index.checked_sub(1).filter(|&previous| {
+                path[previous]
+                    .last()
+                    .and_then(conjugation_of)
+                    .is_some_and(|conjugation| conjugation.is_finite())
+            });
+            Quotation {
+                quotative: index,
+                verb,
+            }
+        })
+        .collect()
+}
+
+/// Returns the mood marked on the final token of the minimum cost path of a lattice.
+///
+/// The mood is marked by a free-standing particle such as "kai"
+/// or a particle attached to the final verb such as "-o".
+/// The path costs of the lattice must be calculated in advance.
+pub fn find_sentence_mood(lattice: &Lattice) -> Option<Mood> {
+    lattice
+        .get_min_cost_path()
+        .last()
+        .and_then(|words| words.last())
+        .and_then(Word::mood)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(sentence: &str) -> Vec<Construction> {
+        let mut lattice = Lattice::from_sentence(sentence);
+        lattice.calculate_path_costs();
+        analyze_constructions(&lattice)
+    }
+
+    #[test]
+    fn test_negative() {
+        let constructions = analyze("bi genehekū");
+        assert_eq!(constructions.len(), 1);
+        assert_eq!(constructions[0].start, 1);
+        assert_eq!(constructions[0].polarity, Polarity::Negative);
+        assert_eq!(constructions[0].aspect, Aspect::Perfective);
+
+        let constructions = analyze("bi generekū");
+        assert_eq!(constructions[0].tense, Tense::NonPast);
+
+        let constructions = analyze("bi genehe akū");
+        assert_eq!(constructions[0].len, 2);
+        assert_eq!(constructions[0].polarity, Polarity::Negative);
+        assert_eq!(constructions[0].tense, Tense::Past);
+
+        // ū written with a combining macron is normalized.
+        let constructions = analyze("bi geneheku\u{304}");
+        assert_eq!(constructions[0].polarity, Polarity::Negative);
+        let constructions = analyze("bi genehe aku\u{304}");
+        assert_eq!(constructions[0].len, 2);
+    }
+
+    #[test]
+    fn test_auxiliary() {
+        let constructions = analyze("bithe tuwame bi");
+        assert_eq!(constructions.len(), 1);
+        assert_eq!(constructions[0].start, 1);
+        assert_eq!(constructions[0].polarity, Polarity::Positive);
+        assert_eq!(constructions[0].aspect, Aspect::Progressive);
+        assert_eq!(constructions[0].tense, Tense::NonPast);
+
+        let constructions = analyze("i genehe bihe");
+        assert_eq!(constructions[0].aspect, Aspect::Perfective);
+        assert_eq!(constructions[0].tense, Tense::Past);
+    }
+
+    #[test]
+    fn test_losing_analysis() {
+        // "ab-ka" is a perfective participle only in an analysis losing to the genitive "abka-i"
+        let constructions = analyze("tere abkai bihe");
+        assert!(constructions.is_empty());
+    }
+
+    #[test]
+    fn test_find_quotations() {
+        // cooha be waki seme tumen cooha be unggifi tosoho. (満文老檔 1 p. 1)
+        let mut lattice =
+            Lattice::from_sentence("cooha be waki seme tumen cooha be unggifi tosoho.");
+        lattice.calculate_path_costs();
+        let quotations = find_quotations(&lattice);
+        assert_eq!(quotations.len(), 1);
+        assert_eq!(quotations[0].quotative, 3);
+        assert_eq!(quotations[0].verb, Some(2));
+    }
+
+    #[test]
+    fn test_find_sentence_mood() {
+        let mut lattice = Lattice::from_sentence("si genehe kai");
+        lattice.calculate_path_costs();
+        assert_eq!(find_sentence_mood(&lattice), Some(Mood::Assertion));
+
+        let mut lattice = Lattice::from_sentence("si genembio");
+        lattice.calculate_path_costs();
+        assert_eq!(find_sentence_mood(&lattice), Some(Mood::Question));
+
+        let mut lattice = Lattice::from_sentence("tere bithe aise");
+        lattice.calculate_path_costs();
+        assert_eq!(find_sentence_mood(&lattice), Some(Mood::Conjecture));
+    }
+}
@@ -0,0 +1,280 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::numeral::{parse_numeral_at, parse_numeral_token};
+
+/// reign era of the Qing dynasty
+#[derive(Clone, Debug, Deserialize)]
+struct Era {
+    /// Manchu name of the era, e.g. "abkai fulingga"
+    name: String,
+    /// Chinese name of the era, e.g. "天命"
+    chinese: String,
+}
+
+/// date expression spanning tokens
+///
+/// For example, "abkai fulingga i ningguci aniya ilan biya juwan de"
+/// is the tenth day of the third month of the sixth year of Tianming.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Date {
+    /// index of the first token of the date
+    pub start: usize,
+    /// number of tokens in the date including the clitics
+    pub len: usize,
+    /// Manchu name of the era
+    pub era: Option<String>,
+    /// Chinese name of the era
+    pub era_chinese: Option<String>,
+    pub year: Option<u64>,
+    pub month: Option<u64>,
+    pub day: Option<u64>,
+}
+
+/// Find date expressions in a sequence of tokens.
+///
+/// A date consists of an era name followed by "i", a year with "aniya",
+/// a month with "biya" and a day, optionally followed by "de".
+/// Each part may be absent but a date has a year or a month,
+/// and a day must follow a month.
+/// A month is from 1 to 12 and a day is from 1 to 30.
+/// The clitics may be attached to the previous token, e.g. "juwande" and "fulinggai".
+pub fn find_dates(tokens: &[&str]) -> Vec<Date> {
+    let eras = &ERA_LIST;
+    let (subtokens, token_indices) = split_date_clitics(tokens, eras);
+    let subtokens: Vec<&str> = subtokens.iter().map(String::as_str).collect();
+    let mut dates = vec![];
+    let mut index = 0;
+    while index < subtokens.len() {
+        match parse_date_at(&subtokens, index, eras) {
+            Some(mut date) => {
+                index += date.len;
+                // Count the original tokens rather than the split ones.
+                date.start = token_indices[date.start];
+                date.len = token_indices[index - 1] + 1 - date.start;
+                dates.push(date);
+            }
+            None => index += 1,
+        }
+    }
+    dates
+}
+
+/// Split the clitics "de" and "i" off the tokens which can end a part of a date.
+///
+/// Returns the split tokens and the index of the original token of each of them.
+fn split_date_clitics(tokens: &[&str], eras: &[Era]) -> (Vec<String>, Vec<usize>) {
+    let mut subtokens = vec![];
+    let mut token_indices = vec![];
+    for (token_index, token) in tokens.iter().enumerate() {
+        let day = token
+            .strip_suffix("de")
+            .filter(|stem| *stem == "inenggi" || parse_numeral_token(stem).is_some())
+            .map(|stem| (stem, "de"));
+        let era = token
+            .strip_suffix('i')
+            .filter(|stem| {
+                eras.iter()
+                    .any(|era| era.name.split_whitespace().last() == Some(*stem))
+            })
+            .map(|stem| (stem, "i"));
+        match day.or(era) {
+            Some((stem, clitic)) => {
+                subtokens.extend([stem.to_string(), clitic.to_string()]);
+                token_indices.extend([token_index, token_index]);
+            }
+            None => {
+                subtokens.push(token.to_string());
+                token_indices.push(token_index);
+            }
+        }
+    }
+    (subtokens, token_indices)
+}
+
+fn parse_date_at(tokens: &[&str], start: usize, eras: &[Era]) -> Option<Date> {
+    let mut index = start;
+
+    let era = eras.iter().find(|era| {
+        let era_tokens: Vec<&str> = era.name.split_whitespace().collect();
+        tokens[index..].starts_with(&era_tokens)
+    });
+    if let Some(era) = era {
+        index += era.name.split_whitespace().count();
+        if tokens.get(index) == Some(&"i") {
+            index += 1;
+        }
+    }
+
+    let year = parse_part(tokens, &mut index, "aniya");
+    let month = parse_month(tokens, &mut index);
+    let day = month.and_then(|_| parse_day(tokens, &mut index));
+    if year.is_none() && month.is_none() {
+        return None;
+    }
+    if tokens.get(index) == Some(&"de") {
+        index += 1;
+    }
+
+    Some(Date {
+        start,
+        len: index - start,
+        era: era.map(|era| era.name.clone()),
+        era_chinese: era.map(|era| era.chinese.clone()),
+        year,
+        month,
+        day,
+    })
+}
+
+/// Parse a numeral followed by `unit`, e.g. "ningguci aniya", and advance `index` past them.
+fn parse_part(tokens: &[&str], index: &mut usize, unit: &str) -> Option<u64> {
+    // "sucungga aniya" is the first year of an era.
+    if unit == "aniya" && tokens.get(*index) == Some(&"sucungga") {
+        if tokens.get(*index + 1) == Some(&unit) {
+            *index += 2;
+            return Some(1);
+        }
+        return None;
+    }
+    let numeral = parse_numeral_at(tokens, *index)?;
+    if tokens.get(*index + numeral.len) != Some(&unit) {
+        return None;
+    }
+    *index += numeral.len + 1;
+    Some(numeral.value)
+}
+
+/// Parse a month such as "ilan biya" or "jorgon biya" and advance `index` past it.
+fn parse_month(tokens: &[&str], index: &mut usize) -> Option<u64> {
+    // The first, the eleventh and the twelfth months have their own names.
+    let named_month = match tokens.get(*index) {
+        Some(&"aniya") => Some(1),
+        Some(&"omšon") => Some(11),
+        Some(&"jorgon") => Some(12),
+        _ => None,
+    };
+    if let Some(month) = named_month {
+        if tokens.get(*index + 1) == Some(&"biya") {
+            *index += 2;
+            return Some(month);
+        }
+    }
+    let mut end = *index;
+    let month = parse_part(tokens, &mut end, "biya").filter(|month| (1..=12).contains(month))?;
+    *index = end;
+    Some(month)
+}
+
+/// Parse a day such as "ice sunja" or "juwan inenggi" and advance `index` past it.
+fn parse_day(tokens: &[&str], index: &mut usize) -> Option<u64> {
+    // "ice" marks the first ten days of a month.
+    let start = match tokens.get(*index) {
+        Some(&"ice") => *index + 1,
+        _ => *index,
+    };
+    let numeral =
+        parse_numeral_at(tokens, start).filter(|numeral| (1..=30).contains(&numeral.value))?;
+    *index = start + numeral.len;
+    if tokens.get(*index) == Some(&"inenggi") {
+        *index += 1;
+    }
+    Some(numeral.value)
+}
+
+/// eras read once for every sentence
+static ERA_LIST: LazyLock<Vec<Era>> = LazyLock::new(|| {
+    let data = include_str!("../resources/era.json");
+    serde_json::from_str(data).expect("JSON was not well-formatted")
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lattice::{Annotation, Lattice};
+
+    #[test]
+    fn test_find_dates() {
+        let tokens: Vec<&str> = "abkai fulingga i ningguci aniya ilan biya juwan de cooha genehe"
+            .split_whitespace()
+            .collect();
+        let dates = find_dates(&tokens);
+        assert_eq!(
+            dates,
+            vec![Date {
+                start: 0,
+                len: 9,
+                era: Some("abkai fulingga".to_string()),
+                era_chinese: Some("天命".to_string()),
+                year: Some(6),
+                month: Some(3),
+                day: Some(10),
+            }]
+        );
+
+        let tokens = ["jorgon", "biya", "ice", "sunja", "de"];
+        let dates = find_dates(&tokens);
+        assert_eq!(dates[0].month, Some(12));
+        assert_eq!(dates[0].day, Some(5));
+        assert_eq!(dates[0].len, 5);
+
+        // A numeral without a unit is not a date.
+        assert!(find_dates(&["ilan", "cooha"]).is_empty());
+    }
+
+    #[test]
+    fn test_date_range() {
+        // "ilan tumen" is not a day but the number of soldiers.
+        let tokens: Vec<&str> = "ilan biya ilan tumen cooha".split_whitespace().collect();
+        let dates = find_dates(&tokens);
+        assert_eq!(dates[0].month, Some(3));
+        assert_eq!(dates[0].day, None);
+        assert_eq!(dates[0].len, 2);
+
+        // There is no thirteenth month.
+        let tokens: Vec<&str> = "juwan ilan biya".split_whitespace().collect();
+        let dates = find_dates(&tokens);
+        assert_eq!(dates[0].start, 1);
+        assert_eq!(dates[0].month, Some(3));
+    }
+
+    #[test]
+    fn test_date_normalization() {
+        // ū written with a combining macron is normalized as the lattice is created.
+        let lattice = Lattice::from_sentence("ijishu\u{304}n dasan i juwan aniya");
+        let dates = lattice
+            .annotations()
+            .iter()
+            .filter_map(|annotation| match annotation {
+                Annotation::Date(date) => Some(date),
+                _ => None,
+            })
+            .collect::<Vec<&Date>>();
+        assert_eq!(dates[0].era, Some("ijishūn dasan".to_string()));
+        assert_eq!(dates[0].year, Some(10));
+
+        // The clitics attached to the previous token
+        let tokens: Vec<&str> = "abkai fulinggai ningguci aniya ilan biya juwande cooha genehe"
+            .split_whitespace()
+            .collect();
+        let dates = find_dates(&tokens);
+        assert_eq!(
+            dates,
+            vec![Date {
+                start: 0,
+                len: 7,
+                era: Some("abkai fulingga".to_string()),
+                era_chinese: Some("天命".to_string()),
+                year: Some(6),
+                month: Some(3),
+                day: Some(10),
+            }]
+        );
+
+        let tokens = ["ilan", "biya", "ice", "sunja", "inenggide"];
+        let dates = find_dates(&tokens);
+        assert_eq!(dates[0].day, Some(5));
+        assert_eq!(dates[0].len, 5);
+    }
+}
@@ -1,21 +1,70 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::LazyLock};
 
 use crate::word::{Case, Detail, PartOfSpeech, Word};
 use serde::Deserialize;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct FunctionWord {
+    /// entry of the function word
+    ///
+    /// An entry spanning several tokens is separated by spaces, e.g. "i jalin".
     pub entry: String,
     pub part_of_speech: PartOfSpeech,
     pub details: Vec<String>,
+    /// condition on the base before the function word if it is a clitic
+    #[serde(default)]
+    pub condition: CliticCondition,
+}
+
+/// phonological condition on the base which a clitic attaches to
+///
+/// Allomorphs of a clitic are distinguished by their conditions,
+/// e.g. genitive "ni" after "ng" and "i" otherwise.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CliticCondition {
+    #[default]
+    Any,
+    /// after a base ending in "ng", e.g. "wang ni"
+    AfterNg,
+    /// after a base not ending in "ng", e.g. "niyalma i"
+    NotAfterNg,
+}
+
+impl CliticCondition {
+    /// Checks if a clitic with the condition can attach to `base`.
+    pub fn is_satisfied_by(&self, base: &str) -> bool {
+        match self {
+            CliticCondition::Any => true,
+            CliticCondition::AfterNg => base.ends_with("ng"),
+            CliticCondition::NotAfterNg => !base.ends_with("ng"),
+        }
+    }
+}
+
+impl FunctionWord {
+    /// Returns the tokens of the entry.
+    pub fn tokens(&self) -> Vec<&str> {
+        self.entry.split_whitespace().collect()
+    }
+
+    /// Returns the emission cost of the function word.
+    ///
+    /// A function word spanning several tokens costs as much as a suffix per token
+    /// so that it competes with the compositional analysis of its tokens.
+    fn emission_cost(&self) -> isize {
+        match self.tokens().len() {
+            1 => -1,
+            len => -5 * len as isize,
+        }
+    }
 }
 
 impl FromStr for FunctionWord {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let function_words = get_function_word_list();
-        for function_word in function_words.iter() {
+        for function_word in get_function_word_list() {
             if function_word.entry == s {
                 return Ok(function_word.clone());
             }
@@ -24,13 +73,13 @@ impl FromStr for FunctionWord {
     }
 }
 
-impl Into<Vec<Word>> for FunctionWord {
+impl From<FunctionWord> for Vec<Word> {
     // when a function word has some details, create a word for each detail
-    fn into(self) -> Vec<Word> {
+    fn from(function_word: FunctionWord) -> Self {
         let mut words = vec![];
 
-        if self.part_of_speech == PartOfSpeech::Clitic {
-            let cases: Result<Vec<Case>, _> = self
+        if function_word.part_of_speech == PartOfSpeech::Clitic {
+            let cases: Result<Vec<Case>, _> = function_word
                 .details
                 .iter()
                 .map(|case| Case::from_str(case))
@@ -40,11 +89,12 @@ impl Into<Vec<Word>> for FunctionWord {
                     let case_words: Vec<Word> = cases
                         .iter()
                         .map(|case| Word {
-                            base: self.entry.clone(),
+                            base: function_word.entry.clone(),
                             suffixes: None,
                             part_of_speech: PartOfSpeech::Clitic,
                             detail: Some(Detail::Case(case.clone())),
-                            emission_cost: -1,
+                            emission_cost: function_word.emission_cost(),
+                            ..Default::default()
                         })
                         .collect();
                     words.extend(case_words);
@@ -55,13 +105,14 @@ impl Into<Vec<Word>> for FunctionWord {
             }
         } else {
             // Create a word for each detail
-            for detail in self.details.iter() {
+            for detail in function_word.details.iter() {
                 let word = Word {
-                    base: self.entry.clone(),
+                    base: function_word.entry.clone(),
                     suffixes: None,
-                    part_of_speech: self.part_of_speech.clone(),
+                    part_of_speech: function_word.part_of_speech,
                     detail: Some(Detail::Other(detail.clone())),
-                    emission_cost: -1,
+                    emission_cost: function_word.emission_cost(),
+                    ..Default::default()
                 };
                 words.push(word);
             }
@@ -71,9 +122,21 @@ impl Into<Vec<Word>> for FunctionWord {
     }
 }
 
-pub fn get_function_word_list() -> Vec<FunctionWord> {
-    let data = include_str!("../resources/function_word.json");
-    let function_words: Vec<FunctionWord> =
-        serde_json::from_str(&data).expect("JSON was not well-formatted");
-    function_words
+/// Returns the function words spanning several tokens.
+pub fn get_multi_token_function_word_list() -> Vec<FunctionWord> {
+    get_function_word_list()
+        .iter()
+        .filter(|function_word| function_word.tokens().len() > 1)
+        .cloned()
+        .collect()
 }
+
+pub fn get_function_word_list() -> &'static [FunctionWord] {
+    &FUNCTION_WORD_LIST
+}
+
+/// function words read once since they are looked up for every token
+static FUNCTION_WORD_LIST: LazyLock<Vec<FunctionWord>> = LazyLock::new(|| {
+    let data = include_str!("../resources/function_word.json");
+    serde_json::from_str(data).expect("JSON was not well-formatted")
+});
@@ -0,0 +1,87 @@
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+use crate::word::{Conjugation, Detail, PartOfSpeech, Word};
+
+/// irregular or suppletive form of a verb
+///
+/// For example, "oho" is the perfective participle of "ombi",
+/// which cannot be analysed by suffix stripping.
+#[derive(Clone, Debug, Deserialize)]
+pub struct IrregularForm {
+    /// surface form, e.g. "jihe"
+    pub surface: String,
+    /// stem of the verb, e.g. "ji"
+    pub stem: String,
+    /// conjugation of the surface form
+    #[serde(rename = "form")]
+    pub conjugation: Conjugation,
+}
+
+impl From<IrregularForm> for Word {
+    fn from(irregular_form: IrregularForm) -> Self {
+        Word {
+            base: irregular_form.stem,
+            suffixes: None,
+            part_of_speech: PartOfSpeech::Verb,
+            detail: Some(Detail::Conjugation(irregular_form.conjugation)),
+            // An entry of the lexicon is more reliable than a segmentation by suffixes.
+            emission_cost: -10,
+            ..Default::default()
+        }
+    }
+}
+
+/// Find the irregular forms whose surface form is `token`.
+pub fn find_irregular_forms(token: &str) -> Vec<IrregularForm> {
+    get_irregular_form_list()
+        .iter()
+        .filter(|irregular_form| irregular_form.surface == token)
+        .cloned()
+        .collect()
+}
+
+pub fn get_irregular_form_list() -> &'static [IrregularForm] {
+    &IRREGULAR_FORM_LIST
+}
+
+/// irregular forms read once since they are looked up for every token
+static IRREGULAR_FORM_LIST: LazyLock<Vec<IrregularForm>> = LazyLock::new(|| {
+    let data = include_str!("../resources/irregular_verb.json");
+    serde_json::from_str(data).expect("JSON was not well-formatted")
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_irregular_forms() {
+        let irregular_forms = find_irregular_forms("jihe");
+        assert_eq!(irregular_forms.len(), 1);
+        assert_eq!(irregular_forms[0].stem, "ji");
+        assert_eq!(
+            irregular_forms[0].conjugation,
+            Conjugation::PerfectiveParticiple
+        );
+
+        let word = Word::from(irregular_forms[0].clone());
+        assert_eq!(word.base, "ji");
+        assert_eq!(word.part_of_speech, PartOfSpeech::Verb);
+        assert_eq!(
+            word.detail,
+            Some(Detail::Conjugation(Conjugation::PerfectiveParticiple))
+        );
+
+        // Suppletive imperatives are listed with the stem of the verb.
+        let irregular_forms = find_irregular_forms("jio");
+        assert_eq!(irregular_forms.len(), 1);
+        assert_eq!(irregular_forms[0].stem, "ji");
+        assert_eq!(irregular_forms[0].conjugation, Conjugation::Imperative);
+
+        // Regular forms are left to suffix stripping.
+        assert!(find_irregular_forms("tuwambi").is_empty());
+        assert!(find_irregular_forms("").is_empty());
+    }
+}
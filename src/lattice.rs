@@ -1,15 +1,26 @@
 use std::vec;
 
 use manchu_converter::ManchuConverter;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 
 use crate::{
+    date::{find_dates, Date},
     edge_cost::get_edge_cost_map,
-    function_word::FunctionWord,
-    phoneme::{is_unusual_final_consonant, is_valid_structure},
-    split_clitic::split_word_into_word_clitic,
-    split_suffix::generate_all_segmentations,
-    word::{Detail, PartOfSpeech, Word},
+    function_word::{get_multi_token_function_word_list, FunctionWord},
+    irregular_verb::find_irregular_forms,
+    mimetic::{find_mimetic, find_reduplications, is_lexical_mimetic, Reduplication},
+    name::{
+        find_capitalized_name, find_name_before_title, find_name_in_gazetteer,
+        get_multi_token_name_list,
+    },
+    numeral::{find_numerals, Numeral},
+    phoneme::{is_unusual_final_consonant, is_valid_structure, normalize},
+    pronoun::find_pronoun_forms,
+    split_clitic::{split_word_into_word_clitics, split_word_into_word_particles},
+    split_suffix::{generate_segmentations, generate_segmentations_before_clitic},
+    syllable::contains_special_syllable,
+    unknown_word::UnknownWordModel,
+    word::{Case, Conjugation, Detail, Features, Origin, PartOfSpeech, Word},
 };
 
 #[derive(Clone, Debug, Serialize)]
@@ -18,6 +29,8 @@ pub struct MorphemeNode {
     ///
     /// If the token includes a clitic, the clitic is indexed as a word.
     /// For example, "niyalmai" is indexed as `vec!["niyalma", "i"]`.
+    /// Each word is serialized with its morphological features.
+    #[serde(serialize_with = "serialize_words_with_features")]
     words: Vec<Word>,
     emission_cost: isize,
     /// minimum cost of path from the beginning to the node
@@ -28,6 +41,28 @@ pub struct MorphemeNode {
     ///
     /// The category indicates the part of speech, conjugation, semantic role and so on.
     category: String,
+    /// number of tokens which the node spans
+    ///
+    /// Basically, the span is 1, but a multi-token function word such as "i jalin" spans several tokens.
+    span: usize,
+}
+
+/// word serialized with the features of `Word::features` as the field `features`
+#[derive(Serialize)]
+struct WordWithFeatures<'a> {
+    #[serde(flatten)]
+    word: &'a Word,
+    features: Features,
+}
+
+fn serialize_words_with_features<S: Serializer>(
+    words: &[Word],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(words.iter().map(|word| WordWithFeatures {
+        word,
+        features: word.features(),
+    }))
 }
 
 impl MorphemeNode {
@@ -38,6 +73,7 @@ impl MorphemeNode {
             path_cost: 0,
             left_node: None,
             category,
+            span: 1,
         }
     }
 
@@ -52,6 +88,14 @@ impl MorphemeNode {
         // calculate emmision cost
         let emision_cost = words.iter().map(|word| word.emission_cost).sum::<isize>();
         let last_word = words.last().unwrap();
+        // an auxiliary is distinguished from the lexical verb by its stem
+        if last_word.part_of_speech == PartOfSpeech::Auxiliary {
+            let category = format!("auxiliary_{}", last_word.base);
+            return MorphemeNode::new(words, emision_cost, category);
+        }
+        if last_word.part_of_speech == PartOfSpeech::Quotative {
+            return MorphemeNode::new(words, emision_cost, "quotative".to_string());
+        }
         let detail = last_word.detail.clone();
         let category;
         match detail {
@@ -72,18 +116,90 @@ impl MorphemeNode {
             }
             None => {
                 category = last_word.part_of_speech.to_string();
-                morpheme_node = MorphemeNode::new(words, 0, category);
+                morpheme_node = MorphemeNode::new(words, emision_cost, category);
             }
         }
         morpheme_node
     }
+
+    /// Create a node of the auxiliary use of the verb in the node.
+    ///
+    /// The auxiliary costs more than the lexical verb
+    /// unless it follows a converb or a participle which it combines with.
+    /// Returns None if the node is not a verb which can be an auxiliary.
+    fn to_auxiliary(&self) -> Option<MorphemeNode> {
+        let [word] = self.words.as_slice() else {
+            return None;
+        };
+        if word.part_of_speech != PartOfSpeech::Verb
+            || !AUXILIARY_STEMS.contains(&word.base.as_str())
+        {
+            return None;
+        }
+        let auxiliary = Word {
+            part_of_speech: PartOfSpeech::Auxiliary,
+            emission_cost: word.emission_cost + AUXILIARY_COST,
+            ..word.clone()
+        };
+        Some(MorphemeNode::from_words(vec![auxiliary]))
+    }
+
+    /// Create a node of the quotative use of the verb "se" in the node.
+    ///
+    /// Returns None if the node is not a quotative form such as "seme" or "sehe".
+    fn to_quotative(&self) -> Option<MorphemeNode> {
+        let [word] = self.words.as_slice() else {
+            return None;
+        };
+        let is_quotative_form = matches!(
+            word.detail,
+            Some(Detail::Conjugation(
+                Conjugation::ImperfectiveConverb
+                    | Conjugation::ProspectiveFinite
+                    | Conjugation::PerfectiveParticiple
+                    | Conjugation::PerfectiveConverb
+                    | Conjugation::ConditionalConverb
+            ))
+        );
+        if word.part_of_speech != PartOfSpeech::Verb || word.base != "se" || !is_quotative_form {
+            return None;
+        }
+        let quotative = Word {
+            part_of_speech: PartOfSpeech::Quotative,
+            // The quotative use is far more frequent than the lexical one.
+            emission_cost: -10,
+            ..word.clone()
+        };
+        Some(MorphemeNode::from_words(vec![quotative]))
+    }
+}
+
+/// Checks if a word is a finite verb or a participle which can be followed by a sentence particle.
+fn can_end_sentence(word: &Word) -> bool {
+    match word.detail {
+        Some(Detail::Conjugation(conjugation)) => {
+            conjugation.is_finite()
+                || conjugation == Conjugation::PerfectiveParticiple
+                || conjugation == Conjugation::PerfectiveProcessiveParticiple
+        }
+        _ => false,
+    }
 }
 
+/// stems of verbs which act as auxiliaries after converbs or participles,
+/// e.g. "mute" in "arame mutembi"
+const AUXILIARY_STEMS: [&str; 6] = ["bi", "o", "se", "mute", "baha", "waji"];
+
+/// additional emission cost of an auxiliary use of a verb
+const AUXILIARY_COST: isize = 5;
+
 /// node separated by a space
 ///
 /// Basically, the node is a word, but it has two words if the word includes a clitic.
 ///
 /// For example, "mini boo" is indexed as `vec!["mini", "boo"]`.
+///
+/// A morpheme node spanning several tokens belongs to the word node of its first token.
 #[derive(Serialize, Clone, Debug)]
 struct WordNode(Vec<MorphemeNode>);
 
@@ -96,40 +212,138 @@ impl WordNode {
         self.0.extend(nodes);
     }
 
+    /// Add the nodes of a name found by `find` in the token or in its base before clitics.
+    ///
+    /// A name is not split into a base and suffixes, but clitics are split off from it.
+    fn add_names(&mut self, token: &str, find: fn(&str) -> Option<Word>) {
+        if let Some(name) = find(token) {
+            self.add_node(MorphemeNode::from_words(vec![name]));
+        }
+        for clitic_split in split_word_into_word_clitics(token).unwrap_or_default() {
+            if let Some(name) = find(&clitic_split.base) {
+                let mut words = vec![name];
+                words.extend(clitic_split.clitics);
+                self.add_node(MorphemeNode::from_words(words));
+            }
+        }
+    }
+
+    /// Checks if some node of the token is a proper name.
+    fn has_name(&self) -> bool {
+        self.0
+            .iter()
+            .any(|node| node.words[0].part_of_speech == PartOfSpeech::ProperNoun)
+    }
+
     fn from_token(token: &str) -> Self {
         let mut word_node = WordNode(vec![]);
-        // If the token ends with an unusual final consonant, it is considered a noun.
-        if is_unusual_final_consonant(&token) {
-            let words = vec![Word {
-                base: token.to_string(),
-                suffixes: None,
-                part_of_speech: PartOfSpeech::Noun,
-                detail: None,
-                emission_cost: 0,
-            }];
-            let morpheme_node = MorphemeNode::from_words(words);
+        // A name in the gazetteer competes with the other analyses of the token.
+        word_node.add_names(token, find_name_in_gazetteer);
+        // The other analyses use the lowercase token,
+        // since a capitalized token is not always a name, e.g. "Bi" at the beginning of a sentence.
+        let token = token.to_lowercase();
+        let token = token.as_str();
+        // If the token contains a syllable for transcribing Chinese, it is considered a loanword
+        // and is not split into a base and native suffixes.
+        if contains_special_syllable(token) {
+            let mut word = Word::new(token.to_string(), None, PartOfSpeech::Noun, None);
+            word.origin = Origin::Chinese;
+            let morpheme_node = MorphemeNode::from_words(vec![word]);
             word_node.add_node(morpheme_node);
             return word_node;
         }
+        // If the token is in the mimetic lexicon, it is not split into a base and suffixes,
+        // but clitics are split off from it.
+        let unknown_word_model = UnknownWordModel::load();
+        if let Some(word) = find_mimetic(token).filter(|_| is_lexical_mimetic(token)) {
+            word_node.add_node(MorphemeNode::from_words(vec![word]));
+        } else {
+            // If the token ends with an unusual final consonant, it is considered a noun
+            // or a mimetic word.
+            if is_unusual_final_consonant(token) {
+                let words = vec![Word::new(token.to_string(), None, PartOfSpeech::Noun, None)];
+                let morpheme_node = MorphemeNode::from_words(words);
+                word_node.add_node(morpheme_node);
+                if let Some(word) = find_mimetic(token) {
+                    word_node.add_node(MorphemeNode::from_words(vec![word]));
+                }
+                return word_node;
+            }
 
-        let all_segmentations = generate_all_segmentations(token, vec![]);
-        for segmentation in all_segmentations {
-            let nodes = MorphemeNode::from_words(vec![segmentation]);
-            word_node.add_node(nodes);
+            // the part of speech of the unsuffixed token is guessed by the unknown word model
+            // and the costs of the other segmentations are calibrated by their bases
+            // A token in a lexicon, e.g. "akū", is not guessed to be a noun or an adjective
+            // but can still be the imperative of a verb, e.g. "dosi".
+            let is_in_lexicon = token.parse::<FunctionWord>().is_ok()
+                || !find_irregular_forms(token).is_empty()
+                || !find_pronoun_forms(token).is_empty();
+            for mut segmentation in generate_segmentations(token) {
+                if segmentation.suffixes.is_some() {
+                    unknown_word_model.calibrate(&mut segmentation);
+                    word_node.add_node(MorphemeNode::from_words(vec![segmentation]));
+                    continue;
+                }
+                if is_in_lexicon {
+                    word_node.add_node(MorphemeNode::from_words(vec![segmentation]));
+                }
+                for word in unknown_word_model.guess(token) {
+                    if !is_in_lexicon || word.part_of_speech == PartOfSpeech::Verb {
+                        word_node.add_node(MorphemeNode::from_words(vec![word]));
+                    }
+                }
+            }
         }
 
-        // if the token includes a clitic, the clitic is indexed as a word
-        if let Ok((word_entry, case_clitics)) = split_word_into_word_clitic(token) {
-            if is_valid_structure(&word_entry) {
-                let all_segmentations = generate_all_segmentations(word_entry.as_str(), vec![]);
-                for segmentation in all_segmentations {
-                    for case_clitic in case_clitics.iter() {
-                        let nodes = MorphemeNode::from_words(vec![
-                            segmentation.clone(),
-                            case_clitic.clone(),
-                        ]);
-                        word_node.add_node(nodes);
+        // if the token includes clitics, each clitic is indexed as a word
+        if let Ok(clitic_splits) = split_word_into_word_clitics(token) {
+            for clitic_split in clitic_splits {
+                if !is_valid_structure(&clitic_split.base) {
+                    continue;
+                }
+                // a mimetic word before a clitic is not split either
+                if is_lexical_mimetic(&clitic_split.base) {
+                    if let Some(word) = find_mimetic(&clitic_split.base) {
+                        let mut words = vec![word];
+                        words.extend(clitic_split.clitics.iter().cloned());
+                        word_node.add_node(MorphemeNode::from_words(words));
                     }
+                    continue;
+                }
+                // the final n is recovered only before a genitive clitic as in "niyalma(n)i",
+                // so that every case reading does not double
+                let is_genitive = matches!(
+                    clitic_split.clitics[0].detail,
+                    Some(Detail::Case(Case::Genitive))
+                );
+                let all_segmentations = if is_genitive {
+                    generate_segmentations_before_clitic(clitic_split.base.as_str())
+                } else {
+                    generate_segmentations(clitic_split.base.as_str())
+                };
+                for mut segmentation in all_segmentations {
+                    unknown_word_model.calibrate(&mut segmentation);
+                    let mut words = vec![segmentation];
+                    words.extend(clitic_split.clitics.iter().cloned());
+                    word_node.add_node(MorphemeNode::from_words(words));
+                }
+            }
+        }
+
+        // if a finite verb or a participle carries a sentence particle, the particle is indexed as a word
+        if let Ok(particle_splits) = split_word_into_word_particles(token) {
+            for particle_split in particle_splits {
+                let verbs = generate_segmentations(particle_split.base.as_str())
+                    .into_iter()
+                    .chain(
+                        find_irregular_forms(&particle_split.base)
+                            .into_iter()
+                            .map(Word::from),
+                    )
+                    .filter(can_end_sentence);
+                for verb in verbs {
+                    let mut words = vec![verb];
+                    words.extend(particle_split.clitics.iter().cloned());
+                    word_node.add_node(MorphemeNode::from_words(words));
                 }
             }
         }
@@ -143,6 +357,33 @@ impl WordNode {
                 .collect();
             word_node.add_nodes(nodes);
         }
+
+        // if the token is an irregular form of a verb, the stem and the conjugation are indexed
+        let nodes: Vec<MorphemeNode> = find_irregular_forms(token)
+            .into_iter()
+            .map(|irregular_form| MorphemeNode::from_words(vec![irregular_form.into()]))
+            .collect();
+        word_node.add_nodes(nodes);
+
+        // if the token is a case form of a pronoun, the pronoun and the case are indexed
+        let nodes: Vec<MorphemeNode> = find_pronoun_forms(token)
+            .into_iter()
+            .map(|pronoun_form| MorphemeNode::from_words(vec![pronoun_form.into()]))
+            .collect();
+        word_node.add_nodes(nodes);
+
+        // if a verb can be an auxiliary, its auxiliary use is also indexed
+        let nodes: Vec<MorphemeNode> = word_node
+            .0
+            .iter()
+            .filter_map(|node| node.to_auxiliary())
+            .collect();
+        word_node.add_nodes(nodes);
+
+        // if the token is a form of "se", its quotative use is also indexed once
+        if let Some(node) = word_node.0.iter().find_map(|node| node.to_quotative()) {
+            word_node.add_node(node);
+        }
         word_node
     }
 
@@ -160,26 +401,146 @@ impl IntoIterator for WordNode {
     }
 }
 
+/// analysis attached to a span of tokens
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Annotation {
+    Numeral(Numeral),
+    Date(Date),
+    Reduplication(Reduplication),
+}
+
 #[derive(Serialize)]
 pub struct Lattice {
     sentence: String,
     lattice: Vec<WordNode>,
+    /// analyses spanning tokens beside the morphological analysis, e.g. numerals or dates
+    annotations: Vec<Annotation>,
 }
 
 impl Lattice {
     /// Create a lattice from a sentence.
     pub fn from_sentence(sentence: &str) -> Self {
-        let space_separated_token: Vec<&str> = sentence.split_whitespace().collect();
+        // The letters of the tokens are written as in the resources.
+        let normalized_sentence = normalize(sentence);
+        let space_separated_token: Vec<&str> = normalized_sentence.split_whitespace().collect();
         let mut lattice = Lattice {
             sentence: sentence.to_string(),
             lattice: vec![WordNode(vec![]); space_separated_token.len()],
+            annotations: vec![],
         };
         for (i, token) in space_separated_token.iter().enumerate() {
             lattice.lattice[i] = WordNode::from_token(token);
         }
+
+        // multi-token function words compete with the analysis of each token
+        for function_word in get_multi_token_function_word_list() {
+            let tokens = function_word.tokens();
+            let span = tokens.len();
+            for i in 0..space_separated_token.len() {
+                if space_separated_token[i..].starts_with(&tokens) {
+                    let words: Vec<Word> = function_word.clone().into();
+                    for word in words {
+                        let mut morpheme_node = MorphemeNode::from_words(vec![word]);
+                        morpheme_node.span = span;
+                        lattice.lattice[i].add_node(morpheme_node);
+                    }
+                }
+            }
+        }
+
+        // multi-token names compete with the analysis of each token
+        for name in get_multi_token_name_list() {
+            let tokens = name.tokens();
+            let span = tokens.len();
+            for i in 0..space_separated_token.len() {
+                if space_separated_token[i..].starts_with(&tokens) {
+                    let mut morpheme_node = MorphemeNode::from_words(vec![name.to_word()]);
+                    morpheme_node.span = span;
+                    lattice.lattice[i].add_node(morpheme_node);
+                }
+            }
+        }
+
+        // a capitalized token can be a name unless it begins the sentence,
+        // but a name in the gazetteer is preferred
+        for (i, token) in space_separated_token.iter().enumerate().skip(1) {
+            if !lattice.lattice[i].has_name() {
+                lattice.lattice[i].add_names(token, find_capitalized_name);
+            }
+        }
+
+        // a token followed by a title such as "beile" can be a personal name
+        for (i, pair) in space_separated_token.windows(2).enumerate() {
+            if let Some(name) = find_name_before_title(pair[0], pair[1]) {
+                lattice.lattice[i].add_node(MorphemeNode::from_words(vec![name]));
+            }
+        }
+
+        // reduplicated tokens compete with the analysis of each token
+        for reduplication in find_reduplications(&space_separated_token) {
+            let word = reduplication.to_word(&space_separated_token);
+            let mut morpheme_node = MorphemeNode::from_words(vec![word]);
+            morpheme_node.span = reduplication.len;
+            lattice.lattice[reduplication.start].add_node(morpheme_node);
+            lattice
+                .annotations
+                .push(Annotation::Reduplication(reduplication));
+        }
+
+        lattice.annotations.extend(
+            find_numerals(&space_separated_token)
+                .into_iter()
+                .map(Annotation::Numeral),
+        );
+        lattice.annotations.extend(
+            find_dates(&space_separated_token)
+                .into_iter()
+                .map(Annotation::Date),
+        );
         lattice
     }
 
+    /// Returns the analyses spanning tokens beside the morphological analysis.
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Returns the number of tokens in the sentence.
+    pub fn token_count(&self) -> usize {
+        self.lattice.len()
+    }
+
+    /// Returns the words of every node which spans only the `index`-th token.
+    pub fn analyses_at(&self, index: usize) -> Vec<Vec<Word>> {
+        self.lattice
+            .get(index)
+            .map(|word_node| {
+                word_node
+                    .0
+                    .iter()
+                    .filter(|node| node.span == 1)
+                    .map(|node| node.words.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the nodes which end just before the `index`-th token.
+    fn nodes_ending_at(&self, index: usize) -> Vec<MorphemeNode> {
+        self.lattice[..index]
+            .iter()
+            .enumerate()
+            .flat_map(|(start, word_node)| {
+                word_node
+                    .0
+                    .iter()
+                    .filter(move |node| start + node.span == index)
+                    .cloned()
+            })
+            .collect()
+    }
+
     /// Serialize a `Lattice` into a JSON string.
     pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(&self)
@@ -203,13 +564,19 @@ impl Lattice {
     /// Calculate the minimum cost path from the beginning to the end of the lattice.
     pub fn calculate_path_costs(&mut self) {
         let edge_cost_map = get_edge_cost_map();
+        // the nodes of the first token have no previous node
+        if let Some(first_word_node) = self.lattice.first_mut() {
+            for morpheme_node in first_word_node.0.iter_mut() {
+                morpheme_node.path_cost = morpheme_node.emission_cost;
+            }
+        }
         for i in 1..self.lattice.len() {
-            let previous_word_node = &self.lattice[i - 1].clone();
+            let previous_nodes = self.nodes_ending_at(i);
             let current_word_node = &mut self.lattice[i];
             for (morpheme_node_index, current_node) in
                 current_word_node.clone().into_iter().enumerate()
             {
-                let min_cost_path = previous_word_node
+                let min_cost_path = previous_nodes
                     .clone()
                     .into_iter()
                     .map(|previous_node| {
@@ -231,6 +598,7 @@ impl Lattice {
                         path_cost,
                         left_node: Some(Box::from(previous_node)),
                         category: current_node.category.clone(),
+                        span: current_node.span,
                     };
                     current_word_node.put_morpheme_node(morpheme_node_index, new_morpheme_node);
                 }
@@ -238,23 +606,40 @@ impl Lattice {
         }
     }
 
+    /// Returns the words of the nodes in the minimum cost path.
+    ///
+    /// A node spanning several tokens appears once in the path.
     pub fn get_min_cost_path(&self) -> Vec<Vec<Word>> {
+        self.get_min_cost_path_with_spans()
+            .into_iter()
+            .map(|(_, _, words)| words)
+            .collect()
+    }
+
+    /// Returns the index of the first token, the span and the words of the nodes in the minimum cost path.
+    pub(crate) fn get_min_cost_path_with_spans(&self) -> Vec<(usize, usize, Vec<Word>)> {
         let mut min_cost_path = vec![];
-        let last_word_node = &self.lattice[self.lattice.len() - 1];
-        let mut min_cost_node = last_word_node.0[0].clone();
-        for word_node in last_word_node.0.iter() {
-            if word_node.path_cost < min_cost_node.path_cost {
-                min_cost_node = word_node.clone();
-            }
-        }
-        min_cost_path.push(min_cost_node.words.clone());
+        let last_nodes = self.nodes_ending_at(self.lattice.len());
+        let min_cost_node = last_nodes
+            .into_iter()
+            .min_by_key(|node| node.path_cost)
+            .unwrap();
+        min_cost_path.push((min_cost_node.span, min_cost_node.words.clone()));
         let mut left_node = min_cost_node.left_node.clone();
         while let Some(node) = left_node {
-            min_cost_path.push(node.words.clone());
+            min_cost_path.push((node.span, node.words.clone()));
             left_node = node.left_node.clone();
         }
         min_cost_path.reverse();
+        let mut start = 0;
         min_cost_path
+            .into_iter()
+            .map(|(span, words)| {
+                let node = (start, span, words);
+                start += span;
+                node
+            })
+            .collect()
     }
 }
 
@@ -263,7 +648,11 @@ mod tests {
     use std::vec;
 
     use super::*;
-    use crate::word::{Conjugation, PartOfSpeech, Suffix, SuffixRole};
+    use crate::word::{StemConstraint, Suffix, SuffixRole};
+    use crate::{
+        phoneme::Harmony,
+        word::{Mood, Number, Person},
+    };
 
     fn create_lattice() -> Lattice {
         // cooha be waki seme tumen cooha be unggifi tosoho. (満文老檔 1 p. 1)
@@ -276,11 +665,13 @@ mod tests {
                     part_of_speech: PartOfSpeech::Noun,
                     detail: None,
                     emission_cost: 0,
+                    ..Default::default()
                 }],
                 emission_cost: 0,
                 path_cost: 0,
                 left_node: None,
                 category: "".to_string(),
+                span: 1,
             },
             MorphemeNode {
                 words: vec![Word {
@@ -290,15 +681,21 @@ mod tests {
                         conjugation: Conjugation::PerfectiveParticiple,
                         role: SuffixRole::Functional,
                         part_of_speech: PartOfSpeech::Noun,
+                        right_part_of_speech: PartOfSpeech::Noun,
+                        harmony: Harmony::Neutral,
+                        stem_constraint: StemConstraint::Any,
+                        cost: -5,
                     }]),
                     part_of_speech: PartOfSpeech::Noun,
                     detail: None,
                     emission_cost: 0,
+                    ..Default::default()
                 }],
                 emission_cost: 0,
                 path_cost: 0,
                 left_node: None,
                 category: "".to_string(),
+                span: 1,
             },
         ]);
         let word_node_1 = WordNode(vec![MorphemeNode {
@@ -308,11 +705,13 @@ mod tests {
                 part_of_speech: PartOfSpeech::Clitic,
                 detail: None,
                 emission_cost: 0,
+                ..Default::default()
             }],
             emission_cost: 0,
             path_cost: 0,
             left_node: None,
             category: "".to_string(),
+            span: 1,
         }]);
         let word_node_2 = WordNode(vec![MorphemeNode {
             words: vec![Word {
@@ -321,11 +720,13 @@ mod tests {
                 part_of_speech: PartOfSpeech::Noun,
                 detail: None,
                 emission_cost: 0,
+                ..Default::default()
             }],
             emission_cost: 0,
             path_cost: 0,
             left_node: None,
             category: "".to_string(),
+            span: 1,
         }]);
         let word_node_3 = WordNode(vec![MorphemeNode {
             words: vec![Word {
@@ -334,11 +735,13 @@ mod tests {
                 part_of_speech: PartOfSpeech::Noun,
                 detail: None,
                 emission_cost: 0,
+                ..Default::default()
             }],
             emission_cost: 0,
             path_cost: 0,
             left_node: None,
             category: "".to_string(),
+            span: 1,
         }]);
         let word_node_4 = WordNode(vec![MorphemeNode {
             words: vec![Word {
@@ -347,11 +750,13 @@ mod tests {
                 part_of_speech: PartOfSpeech::Noun,
                 detail: None,
                 emission_cost: 0,
+                ..Default::default()
             }],
             emission_cost: 0,
             path_cost: 0,
             left_node: None,
             category: "".to_string(),
+            span: 1,
         }]);
         let word_node_5 = WordNode(vec![
             MorphemeNode {
@@ -361,11 +766,13 @@ mod tests {
                     part_of_speech: PartOfSpeech::Noun,
                     detail: None,
                     emission_cost: 0,
+                    ..Default::default()
                 }],
                 emission_cost: 0,
                 path_cost: 0,
                 left_node: None,
                 category: "".to_string(),
+                span: 1,
             },
             MorphemeNode {
                 words: vec![Word {
@@ -375,15 +782,21 @@ mod tests {
                         conjugation: Conjugation::PerfectiveParticiple,
                         role: SuffixRole::Functional,
                         part_of_speech: PartOfSpeech::Noun,
+                        right_part_of_speech: PartOfSpeech::Noun,
+                        harmony: Harmony::Neutral,
+                        stem_constraint: StemConstraint::Any,
+                        cost: -5,
                     }]),
                     part_of_speech: PartOfSpeech::Noun,
                     detail: None,
                     emission_cost: 0,
+                    ..Default::default()
                 }],
                 emission_cost: 0,
                 path_cost: 0,
                 left_node: None,
                 category: "".to_string(),
+                span: 1,
             },
         ]);
         let word_node_6 = WordNode(vec![MorphemeNode {
@@ -393,11 +806,13 @@ mod tests {
                 part_of_speech: PartOfSpeech::Clitic,
                 detail: None,
                 emission_cost: 0,
+                ..Default::default()
             }],
             emission_cost: 0,
             path_cost: 0,
             left_node: None,
             category: "".to_string(),
+            span: 1,
         }]);
         let word_node_7 = WordNode(vec![
             MorphemeNode {
@@ -408,15 +823,21 @@ mod tests {
                         conjugation: Conjugation::PerfectiveConverb,
                         role: SuffixRole::Functional,
                         part_of_speech: PartOfSpeech::Noun,
+                        right_part_of_speech: PartOfSpeech::Noun,
+                        harmony: Harmony::Neutral,
+                        stem_constraint: StemConstraint::Any,
+                        cost: -5,
                     }]),
                     part_of_speech: PartOfSpeech::Noun,
                     detail: None,
                     emission_cost: 0,
+                    ..Default::default()
                 }],
                 emission_cost: 0,
                 path_cost: 0,
                 left_node: None,
                 category: "".to_string(),
+                span: 1,
             },
             MorphemeNode {
                 words: vec![Word {
@@ -426,15 +847,21 @@ mod tests {
                         conjugation: Conjugation::PerfectiveConverb,
                         role: SuffixRole::Functional,
                         part_of_speech: PartOfSpeech::Noun,
+                        right_part_of_speech: PartOfSpeech::Noun,
+                        harmony: Harmony::Neutral,
+                        stem_constraint: StemConstraint::Any,
+                        cost: -5,
                     }]),
                     part_of_speech: PartOfSpeech::Noun,
                     detail: None,
                     emission_cost: 0,
+                    ..Default::default()
                 }],
                 emission_cost: 0,
                 path_cost: 0,
                 left_node: None,
                 category: "".to_string(),
+                span: 1,
             },
         ]);
         let word_node_8 = WordNode(vec![
@@ -445,11 +872,13 @@ mod tests {
                     part_of_speech: PartOfSpeech::Noun,
                     detail: None,
                     emission_cost: 0,
+                    ..Default::default()
                 }],
                 emission_cost: 0,
                 path_cost: 0,
                 left_node: None,
                 category: 0.to_string(),
+                span: 1,
             },
             MorphemeNode {
                 words: vec![Word {
@@ -459,15 +888,21 @@ mod tests {
                         conjugation: Conjugation::PerfectiveParticiple,
                         role: SuffixRole::Functional,
                         part_of_speech: PartOfSpeech::Noun,
+                        right_part_of_speech: PartOfSpeech::Noun,
+                        harmony: Harmony::Neutral,
+                        stem_constraint: StemConstraint::Any,
+                        cost: -5,
                     }]),
                     part_of_speech: PartOfSpeech::Noun,
                     detail: None,
                     emission_cost: 0,
+                    ..Default::default()
                 }],
                 emission_cost: 0,
                 path_cost: 0,
                 left_node: None,
                 category: "".to_string(),
+                span: 1,
             },
             MorphemeNode {
                 words: vec![Word {
@@ -477,18 +912,24 @@ mod tests {
                         conjugation: Conjugation::Plural,
                         role: SuffixRole::Functional,
                         part_of_speech: PartOfSpeech::Noun,
+                        right_part_of_speech: PartOfSpeech::Noun,
+                        harmony: Harmony::Neutral,
+                        stem_constraint: StemConstraint::Any,
+                        cost: -5,
                     }]),
                     part_of_speech: PartOfSpeech::Noun,
                     detail: None,
                     emission_cost: 1,
+                    ..Default::default()
                 }],
                 emission_cost: 1,
                 path_cost: 0,
                 left_node: None,
                 category: "".to_string(),
+                span: 1,
             },
         ]);
-        let lattice = Lattice {
+        Lattice {
             sentence: sentence.to_string(),
             lattice: vec![
                 word_node_0,
@@ -501,8 +942,8 @@ mod tests {
                 word_node_7,
                 word_node_8,
             ],
-        };
-        lattice
+            annotations: vec![],
+        }
     }
 
     #[test]
@@ -510,7 +951,7 @@ mod tests {
         // TODO: まともなテストを書く
         let mut lattice = create_lattice();
         lattice.calculate_path_costs();
-        assert_eq!(lattice.lattice[8].0.get(0).unwrap().path_cost, 0);
+        assert_eq!(lattice.lattice[8].0.first().unwrap().path_cost, 0);
         assert_eq!(lattice.lattice[8].0.get(1).unwrap().path_cost, 0);
     }
 
@@ -518,8 +959,348 @@ mod tests {
     fn test_word_node_from_token() {
         let word_node = WordNode::from_token("niyalmai");
         let len = word_node.0.len();
-        assert_eq!(len, 2);
-        assert_eq!(word_node.0[1].words[0].base, "niyalma");
+        // "niyalmai" itself, "niyalma" with "i" as genitive and nominative,
+        // and "niyalman" with its final n dropped before the genitive "i"
+        assert_eq!(len, 4);
+        let count_base = |base: &str| {
+            word_node
+                .0
+                .iter()
+                .filter(|node| node.words[0].base == base && node.words.len() == 2)
+                .count()
+        };
+        assert_eq!(count_base("niyalma"), 2);
+        assert_eq!(count_base("niyalman"), 1);
+
+        // The recovered stem costs more than the surface one.
+        let cost_of_base = |base: &str| {
+            word_node
+                .0
+                .iter()
+                .filter(|node| node.words[0].base == base && node.words.len() == 2)
+                .map(|node| node.emission_cost)
+                .min()
+                .unwrap()
+        };
+        assert!(cost_of_base("niyalma") < cost_of_base("niyalman"));
+    }
+
+    #[test]
+    fn test_word_node_from_stacked_clitics() {
+        let word_node = WordNode::from_token("niyalmaini");
+        let stacked_node = word_node
+            .0
+            .iter()
+            .find(|node| node.words.len() == 3 && node.words[0].base == "niyalma")
+            .unwrap();
+        assert_eq!(stacked_node.words[1].base, "i");
+        assert_eq!(stacked_node.words[2].base, "ni");
+    }
+
+    #[test]
+    fn test_word_node_from_loanword() {
+        let word_node = WordNode::from_token("ts'ai");
+        assert_eq!(word_node.0.len(), 1);
+        assert_eq!(word_node.0[0].words[0].base, "ts'ai");
+        assert!(word_node.0[0].words[0].is_loanword());
+    }
+
+    #[test]
+    fn test_word_node_from_pronoun() {
+        let word_node = WordNode::from_token("mimbe");
+        let pronoun_node = word_node
+            .0
+            .iter()
+            .find(|node| node.words[0].part_of_speech == PartOfSpeech::Pronoun)
+            .unwrap();
+        assert_eq!(pronoun_node.category, "accusative");
+        assert_eq!(pronoun_node.words[0].base, "bi");
+        assert_eq!(pronoun_node.words[0].person, Some(Person::First));
+        assert_eq!(pronoun_node.words[0].number, Some(Number::Singular));
+
+        // The pronoun is preferred to a segmentation by suffixes or clitics.
+        let mut lattice = Lattice::from_sentence("simbe tuwambi");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path[0][0].part_of_speech, PartOfSpeech::Pronoun);
+    }
+
+    #[test]
+    fn test_auxiliary() {
+        let mut lattice = Lattice::from_sentence("bithe arame mutembi");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path[2][0].part_of_speech, PartOfSpeech::Auxiliary);
+        assert_eq!(path[2][0].base, "mute");
+
+        // "mutembi" without a converb is a lexical verb.
+        let mut lattice = Lattice::from_sentence("bi mutembi");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path[1][0].part_of_speech, PartOfSpeech::Verb);
+    }
+
+    #[test]
+    fn test_attached_particle() {
+        let word_node = WordNode::from_token("genehekai");
+        let particle_node = word_node
+            .0
+            .iter()
+            .find(|node| node.words.len() == 2 && node.words[0].base == "gene")
+            .unwrap();
+        assert_eq!(particle_node.category, "assertive");
+        assert_eq!(particle_node.words[1].mood(), Some(Mood::Assertion));
+    }
+
+    #[test]
+    fn test_word_node_from_irregular_form() {
+        let word_node = WordNode::from_token("oho");
+        let irregular_node = word_node
+            .0
+            .iter()
+            .find(|node| node.words[0].base == "o")
+            .unwrap();
+        assert_eq!(irregular_node.category, "perfective_participle");
+        assert_eq!(irregular_node.words[0].part_of_speech, PartOfSpeech::Verb);
+    }
+
+    #[test]
+    fn test_multi_token_function_word() {
+        let mut lattice = Lattice::from_sentence("gurun i jalin");
+        let multi_token_node = lattice.lattice[1]
+            .0
+            .iter()
+            .find(|node| node.span == 2)
+            .unwrap();
+        assert_eq!(multi_token_node.words[0].base, "i jalin");
+        assert_eq!(multi_token_node.category, "causal");
+
+        lattice.calculate_path_costs();
+        let min_cost_path = lattice.get_min_cost_path();
+        assert_eq!(min_cost_path.len(), 2);
+        assert_eq!(min_cost_path[1][0].base, "i jalin");
+    }
+
+    #[test]
+    fn test_numeral_annotation() {
+        let lattice = Lattice::from_sentence("juwe tangg\u{16b} susai cooha");
+        assert_eq!(lattice.annotations().len(), 1);
+        let Annotation::Numeral(numeral) = &lattice.annotations()[0] else {
+            panic!("not a numeral");
+        };
+        assert_eq!(numeral.value, 250);
+        assert_eq!(numeral.len, 3);
+    }
+
+    #[test]
+    fn test_date_annotation() {
+        let lattice = Lattice::from_sentence("elhe taifin i juwan aniya duin biya de");
+        let date = lattice
+            .annotations()
+            .iter()
+            .find_map(|annotation| match annotation {
+                Annotation::Date(date) => Some(date),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(date.era_chinese.as_deref(), Some("康熙"));
+        assert_eq!(date.year, Some(10));
+        assert_eq!(date.month, Some(4));
+    }
+
+    #[test]
+    fn test_reduplication() {
+        let mut lattice = Lattice::from_sentence("jai jai tuwambi");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path.len(), 2);
+        assert_eq!(path[0][0].base, "jai jai");
+        assert_eq!(path[0][0].part_of_speech, PartOfSpeech::Adverb);
+
+        let word_node = WordNode::from_token("pak");
+        assert_eq!(word_node.0.len(), 1);
+        assert_eq!(word_node.0[0].category, "mimetic");
+
+        // Clitics are split off from a mimetic word.
+        let word_node = WordNode::from_token("pakbe");
+        assert!(word_node.0.iter().any(|node| node.words[0].base == "pak"
+            && node.words[0].part_of_speech == PartOfSpeech::Mimetic
+            && node.category == "accusative"));
+    }
+
+    #[test]
+    fn test_proper_name() {
+        let mut lattice = Lattice::from_sentence("tere Liyoodung de genehe");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path[1][0].base, "Liyoodung");
+        assert_eq!(path[1][0].part_of_speech, PartOfSpeech::ProperNoun);
+
+        // Clitics are split off from a name.
+        let word_node = WordNode::from_token("Nurgacii");
+        assert!(word_node
+            .0
+            .iter()
+            .any(|node| node.words[0].base == "Nurgaci" && node.words.len() == 2));
+
+        // A name competes with the other analyses of the token.
+        let word_node = WordNode::from_token("Liyoodung");
+        assert!(word_node.0.len() > 1);
+
+        // A capitalized token at the beginning of a sentence is not a name by capitalization.
+        let lattice = Lattice::from_sentence("Bi genembi");
+        assert!(lattice.lattice[0]
+            .0
+            .iter()
+            .any(|node| node.words[0].part_of_speech == PartOfSpeech::Pronoun));
+        assert!(!lattice.lattice[0].has_name());
+        let lattice = Lattice::from_sentence("Cooha be unggifi");
+        assert!(lattice.lattice[0]
+            .0
+            .iter()
+            .any(|node| node.words[0].base == "cooha"
+                && node.words[0].part_of_speech == PartOfSpeech::Noun));
+
+        // A capitalized token elsewhere can be a name.
+        let mut lattice = Lattice::from_sentence("tere Hūrhan be waha");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path[1][0].base, "Hūrhan");
+        assert_eq!(path[1][0].part_of_speech, PartOfSpeech::ProperNoun);
+
+        let mut lattice = Lattice::from_sentence("Aisin Gioro hala");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path[0][0].base, "Aisin Gioro");
+
+        let lattice = Lattice::from_sentence("daišan beile");
+        assert!(lattice.lattice[0]
+            .0
+            .iter()
+            .any(|node| node.category == "person_name"));
+
+        // A clitic before a title is not a name.
+        let mut lattice = Lattice::from_sentence("gurun i ejen");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        let parts_of_speech: Vec<PartOfSpeech> =
+            path.iter().map(|words| words[0].part_of_speech).collect();
+        assert_eq!(
+            parts_of_speech,
+            vec![PartOfSpeech::Noun, PartOfSpeech::Clitic, PartOfSpeech::Noun]
+        );
+    }
+
+    #[test]
+    fn test_unknown_word() {
+        // An unseen stem which looks like a verb prefers the verb to the noun
+        // among the unsuffixed analyses.
+        let word_node = WordNode::from_token("dosi");
+        let unsuffixed_cost = |part_of_speech: PartOfSpeech| {
+            word_node
+                .0
+                .iter()
+                .find(|node| {
+                    node.words.len() == 1
+                        && node.words[0].suffixes.is_none()
+                        && node.words[0].part_of_speech == part_of_speech
+                })
+                .unwrap()
+                .emission_cost
+        };
+        assert!(unsuffixed_cost(PartOfSpeech::Verb) < unsuffixed_cost(PartOfSpeech::Noun));
+    }
+
+    #[test]
+    fn test_ambiguous_deverbal_suffix() {
+        // Deverbal "n" and "kū" do not beat the unsplit "tumen" and "akū".
+        let mut lattice = Lattice::from_sentence("juwe tumen cooha akū");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path[1][0].base, "tumen");
+        assert!(path[1][0].suffixes.is_none());
+        assert_eq!(path[3][0].base, "akū");
+        assert!(path[3][0].suffixes.is_none());
+        // The function word is not beaten by a guess of the unknown word model either.
+        assert_eq!(
+            path[3][0].detail,
+            Some(Detail::Other("negative".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_known_verb_stem() {
+        // "genehe" is "gene" with "he", not "ge" with "ne" and "he".
+        let mut lattice = Lattice::from_sentence("bi genehe");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path[1][0].base, "gene");
+        assert_eq!(
+            path[1][0].detail,
+            Some(Detail::Conjugation(Conjugation::PerfectiveParticiple))
+        );
+        assert!(lattice.lattice[1]
+            .0
+            .iter()
+            .all(|node| node.words[0].base != "ge"));
+    }
+
+    #[test]
+    fn test_from_words_without_detail() {
+        // The emission cost of the words is kept even if the last word has no detail.
+        let noun = Word {
+            base: "hafan".to_string(),
+            part_of_speech: PartOfSpeech::Noun,
+            emission_cost: -2,
+            ..Default::default()
+        };
+        let node = MorphemeNode::from_words(vec![noun.clone()]);
+        assert_eq!(node.emission_cost, -2);
+        assert_eq!(node.category, "noun");
+
+        let particle = Word {
+            base: "o".to_string(),
+            part_of_speech: PartOfSpeech::Particle,
+            emission_cost: -1,
+            ..Default::default()
+        };
+        let node = MorphemeNode::from_words(vec![noun, particle]);
+        assert_eq!(node.emission_cost, -3);
+        assert_eq!(node.category, "particle");
+    }
+
+    #[test]
+    fn test_first_token_path_cost() {
+        // The emission cost of the first token counts as well as the others.
+        let mut lattice = Lattice::from_sentence("genembi");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert_eq!(path[0][0].base, "gene");
+        assert_eq!(path[0][0].part_of_speech, PartOfSpeech::Verb);
+
+        let mut lattice = Lattice::from_sentence("hafasa be");
+        lattice.calculate_path_costs();
+        let path = lattice.get_min_cost_path();
+        assert!(path[0][0].suffixes.is_some());
+    }
+
+    #[test]
+    fn test_to_json_string() {
+        // scripts/format_lattice.py reads cases in the original form.
+        let json = Lattice::from_sentence("bade").to_json_string().unwrap();
+        assert!(json.contains(r#"{"Case":"DativeLocative"}"#));
+
+        // The features of each word are serialized beside its fields.
+        let json = Lattice::from_sentence("genehe").to_json_string().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let words = value["lattice"][0]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|node| node["words"].as_array().unwrap());
+        assert!(words
+            .into_iter()
+            .any(|word| word["base"] == "gene" && word["features"]["tense"] == "past"));
     }
 
     #[test]
@@ -0,0 +1,141 @@
+use crate::{
+    function_word::FunctionWord,
+    lattice::Lattice,
+    word::{Detail, PartOfSpeech, SuffixRole, Word},
+};
+
+/// lemmatizer producing dictionary headwords
+///
+/// Verbs are cited as the stem with "mbi", e.g. "tuwambi",
+/// and nouns and adjectives as the bare stem, e.g. "hafan".
+pub struct Lemmatizer {
+    /// number of derivational suffixes kept in the lemma, counted from the root
+    ///
+    /// If it is `None`, all derivational suffixes are kept.
+    /// For example, the lemma of "tuwabumbi" is "tuwabumbi" with `None` or `Some(1)`
+    /// and "tuwambi" with `Some(0)`.
+    derivation_depth: Option<usize>,
+}
+
+impl Lemmatizer {
+    pub fn new(derivation_depth: Option<usize>) -> Self {
+        Lemmatizer { derivation_depth }
+    }
+
+    /// Returns the dictionary headword of a word.
+    ///
+    /// Inflectional suffixes are removed
+    /// and derivational suffixes are kept up to the derivation depth.
+    pub fn lemmatize(&self, word: &Word) -> String {
+        // A function word such as the negative verb "akū" is cited as it is.
+        let is_function_word = matches!(word.detail, Some(Detail::Other(_)))
+            && word.base.parse::<FunctionWord>().is_ok();
+        if is_function_word {
+            return word.base.clone();
+        }
+        let suffixes = word.suffixes.clone().unwrap_or_default();
+        // The order of suffixes is from the right to the left,
+        // so the derivational suffixes next to the root are at the end.
+        let derivational_suffixes = suffixes
+            .iter()
+            .rev()
+            .take_while(|suffix| suffix.role != SuffixRole::Functional)
+            .take(self.derivation_depth.unwrap_or(usize::MAX))
+            .collect::<Vec<_>>();
+
+        let mut stem = word.base.clone();
+        for suffix in derivational_suffixes.iter() {
+            stem.push_str(&suffix.suffix);
+        }
+        let part_of_speech = derivational_suffixes
+            .last()
+            .map_or(word.part_of_speech, |suffix| suffix.right_part_of_speech);
+        // Auxiliaries and quotatives are uses of verbs and share their headword.
+        match part_of_speech {
+            PartOfSpeech::Verb | PartOfSpeech::Auxiliary | PartOfSpeech::Quotative => {
+                format!("{}mbi", stem)
+            }
+            _ => stem,
+        }
+    }
+
+    /// Returns the dictionary headword of each node in the minimum cost path of a lattice.
+    ///
+    /// A node spanning several tokens such as "i jalin" has one headword,
+    /// so the headwords may be fewer than the tokens.
+    /// If a token includes a clitic, the headword of the word before the clitic is returned.
+    /// The path costs of the lattice must be calculated in advance.
+    pub fn lemmatize_lattice(&self, lattice: &Lattice) -> Vec<String> {
+        lattice
+            .get_min_cost_path()
+            .iter()
+            .filter_map(|words| words.first())
+            .map(|word| self.lemmatize(word))
+            .collect()
+    }
+}
+
+impl Default for Lemmatizer {
+    fn default() -> Self {
+        Lemmatizer::new(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split_suffix::generate_segmentations;
+
+    fn find_segmentation(token: &str, base: &str) -> Word {
+        generate_segmentations(token)
+            .into_iter()
+            .find(|word| word.base == base)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_lemmatize() {
+        let word = find_segmentation("tuwabumbi", "tuwa");
+        assert_eq!(Lemmatizer::default().lemmatize(&word), "tuwabumbi");
+        assert_eq!(Lemmatizer::new(Some(1)).lemmatize(&word), "tuwabumbi");
+        assert_eq!(Lemmatizer::new(Some(0)).lemmatize(&word), "tuwambi");
+
+        let word = find_segmentation("hafasa", "hafan");
+        assert_eq!(Lemmatizer::default().lemmatize(&word), "hafan");
+
+        // "ulhicun" is a noun derived from "ulhi".
+        let word = find_segmentation("ulhicun", "ulhi");
+        assert_eq!(Lemmatizer::default().lemmatize(&word), "ulhicun");
+        assert_eq!(Lemmatizer::new(Some(0)).lemmatize(&word), "ulhimbi");
+    }
+
+    #[test]
+    fn test_lemmatize_lattice() {
+        let mut lattice = Lattice::from_sentence("cooha be unggifi");
+        lattice.calculate_path_costs();
+        let lemmas = Lemmatizer::default().lemmatize_lattice(&lattice);
+        assert_eq!(lemmas.len(), 3);
+        assert_eq!(lemmas[2], "unggimbi");
+
+        let mut lattice = Lattice::from_sentence("gurun i jalin");
+        lattice.calculate_path_costs();
+        let lemmas = Lemmatizer::default().lemmatize_lattice(&lattice);
+        assert_eq!(lemmas, vec!["gurun", "i jalin"]);
+
+        let mut lattice = Lattice::from_sentence("bithe arame mutembi");
+        lattice.calculate_path_costs();
+        let lemmas = Lemmatizer::default().lemmatize_lattice(&lattice);
+        assert_eq!(lemmas, vec!["bithe", "arambi", "mutembi"]);
+
+        let mut lattice = Lattice::from_sentence("cooha be waki seme");
+        lattice.calculate_path_costs();
+        let lemmas = Lemmatizer::default().lemmatize_lattice(&lattice);
+        assert_eq!(lemmas[3], "sembi");
+    }
+
+    #[test]
+    fn test_lemmatize_function_word() {
+        let words: Vec<Word> = "akū".parse::<FunctionWord>().unwrap().into();
+        assert_eq!(Lemmatizer::default().lemmatize(&words[0]), "akū");
+    }
+}
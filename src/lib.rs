@@ -1,7 +1,18 @@
+pub mod construction;
+pub mod date;
 mod edge_cost;
 pub mod function_word;
+pub mod irregular_verb;
 pub mod lattice;
+pub mod lemma;
+pub mod mimetic;
+pub mod morphotactics;
+pub mod name;
+pub mod numeral;
 pub mod phoneme;
+pub mod pronoun;
 pub mod split_clitic;
 pub mod split_suffix;
+pub mod syllable;
+pub mod unknown_word;
 pub mod word;
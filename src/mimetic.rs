@@ -0,0 +1,198 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+use crate::{
+    function_word::FunctionWord,
+    phoneme::{ends_with_vowel, is_valid_structure, normalize},
+    syllable::{syllabify, Syllable},
+    word::{Detail, PartOfSpeech, Word},
+};
+
+/// type of a reduplication
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ReduplicationKind {
+    /// the same token repeated, e.g. "jai jai"
+    Full,
+    /// a token repeated with a changed onset or vowel, e.g. "ekšeme saksime" or "kas kis"
+    Partial,
+}
+
+/// reduplication of two adjacent tokens
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Reduplication {
+    /// index of the first token of the reduplication
+    pub start: usize,
+    /// number of tokens in the reduplication
+    pub len: usize,
+    pub kind: ReduplicationKind,
+}
+
+impl Reduplication {
+    /// Create a word of the reduplicated tokens, e.g. "jai jai".
+    pub fn to_word(&self, tokens: &[&str]) -> Word {
+        Word {
+            base: tokens[self.start..self.start + self.len].join(" "),
+            part_of_speech: PartOfSpeech::Adverb,
+            detail: Some(Detail::Other(format!("{}_reduplication", self.kind))),
+            // A reduplication costs as much as a suffix per token
+            // so that it competes with the analysis of each token.
+            emission_cost: -5 * self.len as isize,
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns a mimetic word if the token is one.
+///
+/// A token in the mimetic lexicon is more reliable than one
+/// which only ends with "r" or "s" like most mimetic words, e.g. "kiyatar".
+pub fn find_mimetic(token: &str) -> Option<Word> {
+    let emission_cost = if is_lexical_mimetic(token) {
+        -10
+    } else if (token.ends_with('r') || token.ends_with('s')) && is_valid_structure(token) {
+        -1
+    } else {
+        return None;
+    };
+    Some(Word {
+        base: token.to_string(),
+        part_of_speech: PartOfSpeech::Mimetic,
+        detail: Some(Detail::Other("mimetic".to_string())),
+        emission_cost,
+        ..Default::default()
+    })
+}
+
+/// Checks if a token is in the mimetic lexicon.
+pub fn is_lexical_mimetic(token: &str) -> bool {
+    let token = normalize(token);
+    MIMETIC_LIST.contains(&token)
+}
+
+/// Find reduplications of adjacent tokens.
+///
+/// Two monosyllabic tokens ending with a consonant are a partial reduplication
+/// if they differ only in the vowel, e.g. "kas kis".
+/// Two polysyllabic tokens are a partial reduplication if they have the same number of syllables,
+/// share the last syllable and the end of the first one, and differ in the vowel of the first one,
+/// e.g. "ekšeme saksime".
+/// Function words are never partially reduplicated, so "bi be" is not a reduplication.
+pub fn find_reduplications(tokens: &[&str]) -> Vec<Reduplication> {
+    let mut reduplications = vec![];
+    let mut index = 0;
+    while index + 1 < tokens.len() {
+        let kind = reduplication_kind(tokens[index], tokens[index + 1]);
+        match kind {
+            Some(kind) => {
+                reduplications.push(Reduplication {
+                    start: index,
+                    len: 2,
+                    kind,
+                });
+                index += 2;
+            }
+            None => index += 1,
+        }
+    }
+    reduplications
+}
+
+fn reduplication_kind(left: &str, right: &str) -> Option<ReduplicationKind> {
+    if left == right {
+        return Some(ReduplicationKind::Full);
+    }
+    let (Ok(left_syllables), Ok(right_syllables)) = (syllabify(left), syllabify(right)) else {
+        return None;
+    };
+    if left_syllables.len() != right_syllables.len() {
+        return None;
+    }
+    if left.parse::<FunctionWord>().is_ok() || right.parse::<FunctionWord>().is_ok() {
+        return None;
+    }
+    let is_partial = if left_syllables.len() == 1 {
+        // only the vowel is changed, e.g. "kas kis"
+        !ends_with_vowel(left)
+            && consonants(left) == consonants(right)
+            && vowels(left) != vowels(right)
+    } else {
+        // the first syllable is changed and the rest is kept, e.g. "ekšeme saksime",
+        // but not only its onset as in "baime gaime"
+        let first = |syllables: &[Syllable]| syllables[0].syllable.chars().last();
+        left_syllables.last() == right_syllables.last()
+            && first(&left_syllables) == first(&right_syllables)
+            && vowels(&left_syllables[0].syllable) != vowels(&right_syllables[0].syllable)
+    };
+    if is_partial {
+        Some(ReduplicationKind::Partial)
+    } else {
+        None
+    }
+}
+
+/// Returns the consonants of a token in order.
+fn consonants(token: &str) -> String {
+    token.chars().filter(|c| !"aeiouū".contains(*c)).collect()
+}
+
+/// Returns the vowels of a token in order.
+fn vowels(token: &str) -> String {
+    token.chars().filter(|c| "aeiouū".contains(*c)).collect()
+}
+
+/// mimetic words read once since every token is looked up
+static MIMETIC_LIST: LazyLock<Vec<String>> = LazyLock::new(|| {
+    let data = include_str!("../resources/mimetic.json");
+    serde_json::from_str(data).expect("JSON was not well-formatted")
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_reduplications() {
+        let reduplications = find_reduplications(&["ulhiyen", "ulhiyen", "genehe"]);
+        assert_eq!(
+            reduplications,
+            vec![Reduplication {
+                start: 0,
+                len: 2,
+                kind: ReduplicationKind::Full,
+            }]
+        );
+
+        let reduplications = find_reduplications(&["ekšeme", "saksime"]);
+        assert_eq!(reduplications[0].kind, ReduplicationKind::Partial);
+        let reduplications = find_reduplications(&["kas", "kis"]);
+        assert_eq!(reduplications[0].kind, ReduplicationKind::Partial);
+
+        // Converbs with the same suffix are not a reduplication.
+        assert!(find_reduplications(&["arame", "bime"]).is_empty());
+        assert!(find_reduplications(&["baime", "gaime"]).is_empty());
+        // A pronoun and a case particle are not a reduplication.
+        assert!(find_reduplications(&["bi", "be"]).is_empty());
+        assert!(find_reduplications(&["si", "se"]).is_empty());
+    }
+
+    #[test]
+    fn test_find_mimetic() {
+        let word = find_mimetic("pak").unwrap();
+        assert_eq!(word.part_of_speech, PartOfSpeech::Mimetic);
+        assert_eq!(word.emission_cost, -10);
+        assert_eq!(find_mimetic("kiyatar").unwrap().emission_cost, -10);
+        assert_eq!(find_mimetic("kalir").unwrap().emission_cost, -1);
+        assert!(find_mimetic("cooha").is_none());
+    }
+
+    #[test]
+    fn test_is_lexical_mimetic() {
+        assert!(is_lexical_mimetic("hūwalar"));
+        assert!(is_lexical_mimetic("hu\u{304}r"));
+        assert!(!is_lexical_mimetic("kalir"));
+    }
+}
@@ -0,0 +1,165 @@
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+use crate::word::{Conjugation, PartOfSpeech, Suffix, SuffixRole};
+
+/// morphotactic class of a suffix
+///
+/// The class is a state of the morphotactic automaton.
+/// It is decided by the role of the suffix, its parts of speech
+/// and, for functional suffixes, its conjugation.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SuffixClass {
+    /// derivational suffix making a verb, e.g. "bu"
+    VerbDerivation,
+    /// derivational suffix making a noun or an adjective, e.g. "kan"
+    NounDerivation,
+    /// suffix deriving a noun from a verb
+    Deverbal,
+    /// suffix deriving an adjective from a noun, e.g. "ngge"
+    DenominalAdjective,
+    /// finite suffix, e.g. "mbi" or "habi"
+    Finite,
+    /// participle suffix, e.g. "ha" or "mbihe"
+    Participle,
+    /// converb suffix, e.g. "me" or "fi"
+    Converb,
+    /// inflectional suffix attaching to a noun
+    NominalInflection,
+    /// particle suffix, e.g. interrogative "o"
+    Particle,
+}
+
+impl SuffixClass {
+    pub fn of(suffix: &Suffix) -> Self {
+        match suffix.role {
+            SuffixRole::Derivational => match suffix.right_part_of_speech {
+                PartOfSpeech::Verb => SuffixClass::VerbDerivation,
+                _ => SuffixClass::NounDerivation,
+            },
+            SuffixRole::Deverbal => SuffixClass::Deverbal,
+            SuffixRole::Denominaladjective => SuffixClass::DenominalAdjective,
+            SuffixRole::Functional => match suffix.conjugation {
+                conjugation if conjugation.is_finite() => SuffixClass::Finite,
+                Conjugation::PerfectiveParticiple
+                | Conjugation::PerfectiveProcessiveParticiple
+                | Conjugation::NegativeParticle => SuffixClass::Participle,
+                Conjugation::Interrogative => SuffixClass::Particle,
+                _ if suffix.part_of_speech == PartOfSpeech::Noun => SuffixClass::NominalInflection,
+                _ => SuffixClass::Converb,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Transition {
+    left_class: SuffixClass,
+    right_class: SuffixClass,
+}
+
+/// allowed transitions between suffix classes
+///
+/// A transition from a left class to a right class means that
+/// a suffix of the left class can be followed by a suffix of the right class.
+/// For example, "bu" (verb derivation) can be followed by "mbi" (finite),
+/// but "mbi" cannot be followed by "ha" (participle).
+#[derive(Debug, Deserialize)]
+pub struct Morphotactics {
+    transitions: Vec<Transition>,
+    /// classes which can end a word
+    ///
+    /// A verb derivation is not final since a verb stem needs an inflection,
+    /// e.g. "gene" is not "ge" with the centrifugal "ne".
+    final_classes: Vec<SuffixClass>,
+}
+
+/// morphotactics read once since it is consulted at every split of a suffix
+static MORPHOTACTICS: LazyLock<Morphotactics> = LazyLock::new(|| {
+    let data = include_str!("../resources/morphotactics.json");
+    serde_json::from_str(data).expect("JSON was not well-formatted")
+});
+
+impl Morphotactics {
+    /// Returns the morphotactics read once from the resource.
+    pub fn load() -> &'static Self {
+        &MORPHOTACTICS
+    }
+
+    /// Checks if `left` can be directly followed by `right` in a word.
+    ///
+    /// If `left` is not functional, the part of speech it makes
+    /// must be the one which `right` attaches to.
+    pub fn allows(&self, left: &Suffix, right: &Suffix) -> bool {
+        if left.role != SuffixRole::Functional && left.right_part_of_speech != right.part_of_speech
+        {
+            return false;
+        }
+        let left_class = SuffixClass::of(left);
+        let right_class = SuffixClass::of(right);
+        self.transitions
+            .iter()
+            .any(|t| t.left_class == left_class && t.right_class == right_class)
+    }
+
+    /// Checks if `suffix` can be the last suffix of a word.
+    pub fn can_end_word(&self, suffix: &Suffix) -> bool {
+        self.final_classes.contains(&SuffixClass::of(suffix))
+    }
+
+    /// Checks if a chain of suffixes is well-formed.
+    ///
+    /// The order of suffixes is from the right to the left as in `Word::suffixes`.
+    pub fn allows_chain(&self, suffixes: &[Suffix]) -> bool {
+        suffixes
+            .windows(2)
+            .all(|pair| self.allows(&pair[1], &pair[0]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{phoneme::Harmony, word::StemConstraint};
+
+    fn suffix(suffix: &str, conjugation: Conjugation, role: SuffixRole) -> Suffix {
+        Suffix {
+            suffix: suffix.to_string(),
+            conjugation,
+            role,
+            part_of_speech: PartOfSpeech::Verb,
+            right_part_of_speech: PartOfSpeech::Verb,
+            harmony: Harmony::Neutral,
+            stem_constraint: StemConstraint::Any,
+            cost: -5,
+        }
+    }
+
+    #[test]
+    fn test_allows() {
+        let morphotactics = Morphotactics::load();
+        let bu = suffix(
+            "bu",
+            Conjugation::PassiveCausativeVerbal,
+            SuffixRole::Derivational,
+        );
+        let mbi = suffix(
+            "mbi",
+            Conjugation::ImperfectiveFinite,
+            SuffixRole::Functional,
+        );
+        let ha = suffix(
+            "ha",
+            Conjugation::PerfectiveParticiple,
+            SuffixRole::Functional,
+        );
+        assert!(morphotactics.allows(&bu, &mbi));
+        assert!(!morphotactics.allows(&mbi, &ha));
+        assert!(morphotactics.allows_chain(&[mbi.clone(), bu.clone()]));
+        assert!(morphotactics.can_end_word(&mbi));
+        assert!(!morphotactics.can_end_word(&bu));
+        assert!(!morphotactics.allows_chain(&[ha, mbi, bu]));
+    }
+}
@@ -0,0 +1,162 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+use crate::{
+    function_word::FunctionWord,
+    phoneme::normalize,
+    pronoun::find_pronoun_forms,
+    word::{Detail, PartOfSpeech, Word},
+};
+
+/// type of a proper name
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum NameKind {
+    /// e.g. "Nurgaci"
+    Person,
+    /// e.g. "Aisin Gioro"
+    Clan,
+    /// e.g. "Liyoodung"
+    Place,
+    /// e.g. "gulu suwayan"
+    Banner,
+    /// name found by a heuristic without its type
+    Unknown,
+}
+
+/// entry of the name gazetteer
+#[derive(Clone, Debug, Deserialize)]
+pub struct Name {
+    /// name spanning one or more tokens, e.g. "Aisin Gioro"
+    pub name: String,
+    pub kind: NameKind,
+}
+
+/// words which follow a personal name, e.g. "Daišan beile"
+const TITLES: [&str; 8] = [
+    "beile", "amban", "han", "taiji", "janggin", "ejen", "age", "beise",
+];
+
+/// emission cost of a name found by a heuristic
+///
+/// Capitalization and a following title are only hints,
+/// so the name is preferred to a guess by the unknown word model
+/// but costs less than a suffix and competes with the lexical analyses.
+const HEURISTIC_NAME_COST: isize = -3;
+
+impl Name {
+    /// Returns the tokens of the name.
+    pub fn tokens(&self) -> Vec<&str> {
+        self.name.split_whitespace().collect()
+    }
+
+    /// Create a proper noun from the name.
+    ///
+    /// A name in the gazetteer costs as much as a suffix per token
+    /// and twice as much as a suffix if it is a single token,
+    /// which is more reliable than a name found by a heuristic.
+    pub fn to_word(&self) -> Word {
+        let emission_cost = match self.tokens().len() {
+            1 => -10,
+            len => -5 * len as isize,
+        };
+        to_proper_noun(&self.name, self.kind, emission_cost)
+    }
+}
+
+fn to_proper_noun(name: &str, kind: NameKind, emission_cost: isize) -> Word {
+    let category = match kind {
+        NameKind::Unknown => "proper_noun".to_string(),
+        kind => format!("{}_name", kind),
+    };
+    Word {
+        base: name.to_string(),
+        part_of_speech: PartOfSpeech::ProperNoun,
+        detail: Some(Detail::Other(category)),
+        emission_cost,
+        ..Default::default()
+    }
+}
+
+/// Returns a proper noun if the token is a single-token name in the gazetteer.
+pub fn find_name_in_gazetteer(token: &str) -> Option<Word> {
+    let token = normalize(token);
+    get_name_list()
+        .iter()
+        .find(|name| name.tokens().len() == 1 && name.name == token)
+        .map(|name| name.to_word())
+}
+
+/// Returns a proper noun if the token is capitalized in the romanization.
+pub fn find_capitalized_name(token: &str) -> Option<Word> {
+    if !token.chars().next().is_some_and(|c| c.is_uppercase()) {
+        return None;
+    }
+    Some(to_proper_noun(
+        token,
+        NameKind::Unknown,
+        HEURISTIC_NAME_COST,
+    ))
+}
+
+/// Returns a personal name if the token is followed by a title such as "beile".
+///
+/// Function words including clitics and pronouns are not names, e.g. "i" in "gurun i ejen".
+pub fn find_name_before_title(token: &str, next_token: &str) -> Option<Word> {
+    if !TITLES.contains(&next_token)
+        || token.parse::<FunctionWord>().is_ok()
+        || !find_pronoun_forms(token).is_empty()
+    {
+        return None;
+    }
+    Some(to_proper_noun(token, NameKind::Person, HEURISTIC_NAME_COST))
+}
+
+/// Returns the names spanning several tokens, e.g. "Aisin Gioro".
+pub fn get_multi_token_name_list() -> Vec<Name> {
+    get_name_list()
+        .iter()
+        .filter(|name| name.tokens().len() > 1)
+        .cloned()
+        .collect()
+}
+
+pub fn get_name_list() -> &'static [Name] {
+    &NAME_LIST
+}
+
+/// names read once since every token is looked up in the gazetteer
+static NAME_LIST: LazyLock<Vec<Name>> = LazyLock::new(|| {
+    let data = include_str!("../resources/name.json");
+    serde_json::from_str(data).expect("JSON was not well-formatted")
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_name() {
+        let word = find_name_in_gazetteer("Liyoodung").unwrap();
+        assert_eq!(word.part_of_speech, PartOfSpeech::ProperNoun);
+        assert_eq!(word.detail, Some(Detail::Other("place_name".to_string())));
+        assert!(find_name_in_gazetteer("Hūrhan").is_none());
+        // ū written with a combining macron matches the precomposed ū of the gazetteer.
+        let word = find_name_in_gazetteer("Sarhu\u{304}").unwrap();
+        assert_eq!(word.base, "Sarhū");
+        assert!(find_name_in_gazetteer("Manggūltai").is_some());
+
+        let word = find_capitalized_name("Hūrhan").unwrap();
+        assert_eq!(word.detail, Some(Detail::Other("proper_noun".to_string())));
+        assert_eq!(word.emission_cost, HEURISTIC_NAME_COST);
+
+        assert!(find_capitalized_name("cooha").is_none());
+        assert!(find_name_before_title("daišan", "beile").is_some());
+        assert!(find_name_before_title("daišan", "cooha").is_none());
+        assert!(find_name_before_title("i", "ejen").is_none());
+        assert!(find_name_before_title("bi", "han").is_none());
+    }
+}
@@ -0,0 +1,268 @@
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::Display;
+
+/// entry of the numeral lexicon
+#[derive(Clone, Debug, Deserialize)]
+struct NumeralEntry {
+    /// cardinal form, e.g. "ilan"
+    cardinal: String,
+    value: u64,
+    /// ordinal forms, e.g. "ilaci"
+    ///
+    /// Some numerals have a suppletive ordinal, e.g. "jai" for "juwe".
+    ordinals: Vec<String>,
+    /// distributive form, e.g. "ilata"
+    distributive: String,
+}
+
+/// type of a numeral
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum NumeralKind {
+    /// e.g. "ilan" (three)
+    Cardinal,
+    /// e.g. "ilaci" (third)
+    Ordinal,
+    /// e.g. "ilata" (three each)
+    Distributive,
+}
+
+/// numeral spanning one or more tokens
+///
+/// For example, "juwe tanggū susai" is a cardinal numeral whose value is 250.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Numeral {
+    /// index of the first token of the numeral
+    pub start: usize,
+    /// number of tokens in the numeral
+    pub len: usize,
+    pub value: u64,
+    /// type of the numeral decided by its last token
+    pub kind: NumeralKind,
+}
+
+/// Returns the value and the type of a single numeral token.
+///
+/// Returns None if the token is not a numeral.
+pub fn parse_numeral_token(token: &str) -> Option<(u64, NumeralKind)> {
+    NUMERAL_ENTRY_LIST.iter().find_map(|entry| {
+        if entry.cardinal == token {
+            Some((entry.value, NumeralKind::Cardinal))
+        } else if entry.ordinals.iter().any(|ordinal| ordinal == token) {
+            Some((entry.value, NumeralKind::Ordinal))
+        } else if entry.distributive == token {
+            Some((entry.value, NumeralKind::Distributive))
+        } else {
+            None
+        }
+    })
+}
+
+/// nouns of rank before which "uju" (head) is the ordinal "first", e.g. "uju jergi"
+const RANK_NOUNS: [&str; 4] = ["jergi", "debtelin", "meyen", "fiyelen"];
+
+/// Find numerals in a sequence of tokens.
+///
+/// Consecutive numeral tokens are combined into one numeral
+/// only if they are in the order of a compound numeral;
+/// a number below ten before "tanggū" or "minggan", or any number before "tumen", multiplies it,
+/// and a number smaller than the previous place is added.
+/// For example, "juwe ilan" is two numerals, not five.
+/// Only the last token of a numeral may be an ordinal or a distributive.
+pub fn find_numerals(tokens: &[&str]) -> Vec<Numeral> {
+    let mut numerals = vec![];
+    let mut index = 0;
+    while index < tokens.len() {
+        match parse_numeral_at(tokens, index) {
+            Some(numeral) => {
+                index += numeral.len;
+                numerals.push(numeral);
+            }
+            None => index += 1,
+        }
+    }
+    numerals
+}
+
+/// Parse the longest numeral starting at the `index`-th token.
+///
+/// Returns None if the token is not a numeral.
+pub fn parse_numeral_at(tokens: &[&str], index: usize) -> Option<Numeral> {
+    // "uju" is "head" unless it precedes a noun of rank.
+    if tokens.get(index) == Some(&"uju") {
+        return tokens
+            .get(index + 1)
+            .is_some_and(|noun| RANK_NOUNS.contains(noun))
+            .then_some(Numeral {
+                start: index,
+                len: 1,
+                value: 1,
+                kind: NumeralKind::Ordinal,
+            });
+    }
+    let mut accumulator = NumeralAccumulator::default();
+    let mut kind = NumeralKind::Cardinal;
+    let mut len = 0;
+    while let Some((value, token_kind)) = tokens
+        .get(index + len)
+        .and_then(|token| parse_numeral_token(token))
+    {
+        if !accumulator.accepts(value) {
+            break;
+        }
+        accumulator.add(value);
+        kind = token_kind;
+        len += 1;
+        if token_kind != NumeralKind::Cardinal {
+            break;
+        }
+    }
+    if len == 0 {
+        return None;
+    }
+    Some(Numeral {
+        start: index,
+        len,
+        value: accumulator.value(),
+        kind,
+    })
+}
+
+/// accumulator of the value of a compound numeral
+#[derive(Default)]
+struct NumeralAccumulator {
+    /// sum of the groups of ten thousands
+    myriads: u64,
+    /// sum of the hundreds and the thousands in the current group
+    hundreds: u64,
+    /// smallest of "tanggū" and "minggan" in the current group
+    ///
+    /// If it is 0, the current group has neither of them.
+    place: u64,
+    /// sum of the numbers below a hundred in the current group
+    units: u64,
+}
+
+impl NumeralAccumulator {
+    /// Returns whether `value` can follow the numbers accumulated so far.
+    fn accepts(&self, value: u64) -> bool {
+        match value {
+            // "tumen" is the largest place and appears only once.
+            10000.. => self.myriads == 0,
+            // "juwe tanggū" and "minggan tanggū", but not "juwan tanggū" or "tanggū minggan"
+            100.. => self.units < 10 && (self.place == 0 || value < self.place),
+            // "juwan emu", but not "juwe ilan" or "emu juwan"
+            _ => self.units == 0 || (self.units % 10 == 0 && value < 10),
+        }
+    }
+
+    fn add(&mut self, value: u64) {
+        match value {
+            10000.. => {
+                self.myriads += (self.hundreds + self.units).max(1) * value;
+                self.hundreds = 0;
+                self.place = 0;
+                self.units = 0;
+            }
+            100.. => {
+                self.hundreds += self.units.max(1) * value;
+                self.place = value;
+                self.units = 0;
+            }
+            _ => self.units += value,
+        }
+    }
+
+    fn value(&self) -> u64 {
+        self.myriads + self.hundreds + self.units
+    }
+}
+
+/// numerals read once since every token is looked up
+static NUMERAL_ENTRY_LIST: LazyLock<Vec<NumeralEntry>> = LazyLock::new(|| {
+    let data = include_str!("../resources/numeral.json");
+    serde_json::from_str(data).expect("JSON was not well-formatted")
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_numeral_token() {
+        assert_eq!(
+            parse_numeral_token("ilan"),
+            Some((3, NumeralKind::Cardinal))
+        );
+        assert_eq!(parse_numeral_token("jai"), Some((2, NumeralKind::Ordinal)));
+        assert_eq!(
+            parse_numeral_token("ilata"),
+            Some((3, NumeralKind::Distributive))
+        );
+        assert_eq!(
+            parse_numeral_token("tangg\u{16b}"),
+            Some((100, NumeralKind::Cardinal))
+        );
+        assert_eq!(parse_numeral_token("cooha"), None);
+    }
+
+    #[test]
+    fn test_find_numerals() {
+        let numerals = find_numerals(&["juwe", "tanggū", "susai", "cooha"]);
+        assert_eq!(
+            numerals,
+            vec![Numeral {
+                start: 0,
+                len: 3,
+                value: 250,
+                kind: NumeralKind::Cardinal,
+            }]
+        );
+
+        let numerals = find_numerals(&["minggan", "juwe", "tanggū", "sunja", "tumen"]);
+        assert_eq!(numerals[0].value, 12_050_000);
+
+        let numerals = find_numerals(&["juwan", "emuci", "aniya"]);
+        assert_eq!(numerals[0].value, 11);
+        assert_eq!(numerals[0].kind, NumeralKind::Ordinal);
+
+        // An ordinal ends a numeral.
+        let numerals = find_numerals(&["ilaci", "duin"]);
+        assert_eq!(numerals.len(), 2);
+    }
+
+    #[test]
+    fn test_numeral_order() {
+        // Adjacent numerals out of the order of a compound numeral are not combined.
+        let numerals = find_numerals(&["juwe", "ilan"]);
+        assert_eq!(numerals.len(), 2);
+        assert_eq!(numerals[0].value, 2);
+        assert_eq!(numerals[1].value, 3);
+
+        let numerals = find_numerals(&["emu", "juwan"]);
+        assert_eq!(numerals.len(), 2);
+
+        let numerals = find_numerals(&["tanggū", "minggan"]);
+        assert_eq!(numerals.len(), 2);
+
+        let numerals = find_numerals(&["tumen", "tumen"]);
+        assert_eq!(numerals.len(), 2);
+
+        let numerals = find_numerals(&["minggan", "tanggū", "juwan", "emu"]);
+        assert_eq!(numerals.len(), 1);
+        assert_eq!(numerals[0].value, 1111);
+    }
+
+    #[test]
+    fn test_uju() {
+        let numerals = find_numerals(&["uju", "jergi", "hafan"]);
+        assert_eq!(numerals[0].value, 1);
+        assert_eq!(numerals[0].kind, NumeralKind::Ordinal);
+
+        // "uju" is "head" elsewhere.
+        assert!(find_numerals(&["uju", "be", "lasihimbi"]).is_empty());
+    }
+}
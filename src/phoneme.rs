@@ -1,6 +1,31 @@
 use manchu_converter::ManchuConverter;
+use serde::{Deserialize, Serialize};
 use unicode_segmentation::UnicodeSegmentation;
 
+/// letters written with a combining diacritic and their precomposed forms
+const COMBINING_LETTERS: [(&str, &str); 6] = [
+    ("u\u{304}", "ū"),
+    ("U\u{304}", "Ū"),
+    ("s\u{30c}", "š"),
+    ("S\u{30c}", "Š"),
+    ("z\u{30c}", "ž"),
+    ("Z\u{30c}", "Ž"),
+];
+
+/// Normalize a romanized token so that its letters are written as in the resources.
+///
+/// A letter with a diacritic is precomposed,
+/// e.g. "aku\u{304}" with a combining macron becomes "akū".
+pub fn normalize(token: &str) -> String {
+    let mut token = token.to_string();
+    for (combining, precomposed) in COMBINING_LETTERS {
+        if token.contains(combining) {
+            token = token.replace(combining, precomposed);
+        }
+    }
+    token
+}
+
 /// Checks if a token is a valid phoneme structure.
 ///
 /// The patterns for valid Manchu syllables are:
@@ -25,17 +50,17 @@ pub fn is_valid_structure(token: &str) -> bool {
     }
     // 1文字の場合
     if len == 1 {
-        return is_vowel(graphemes[0]);
+        is_vowel(graphemes[0])
     }
     // 2文字以上かつ、語末に子音が連続して現れる場合
     else if is_consonant(graphemes[len - 1]) && is_consonant(graphemes[len - 2]) {
-        return false;
+        false
     }
     // 2文字以上かつ、語末に子音が現れる場合
     else if is_consonant(graphemes[len - 1]) {
-        return is_valid_last_consonant(graphemes[len - 1]);
+        is_valid_last_consonant(graphemes[len - 1])
     } else {
-        return true;
+        true
     }
 }
 
@@ -82,3 +107,72 @@ fn is_valid_last_consonant(char: &str) -> bool {
         .collect::<Vec<String>>();
     last_consonants.contains(&char.to_string())
 }
+
+/// vowel harmony class of a stem or a suffix
+///
+/// The masculine vowels are a and ū, the feminine vowel is e,
+/// and i and u are neutral.
+/// The rounded class is for o, which takes masculine suffixes as well as rounded ones.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Harmony {
+    Masculine,
+    Feminine,
+    Rounded,
+    Neutral,
+}
+
+/// Returns the harmony class of a romanized token.
+///
+/// The class is decided by the last non-neutral vowel of the token.
+pub fn vowel_harmony(token: &str) -> Harmony {
+    for c in token.chars().rev() {
+        match c {
+            'a' | 'ū' | 'v' => return Harmony::Masculine,
+            'e' => return Harmony::Feminine,
+            'o' => return Harmony::Rounded,
+            _ => continue,
+        }
+    }
+    Harmony::Neutral
+}
+
+/// Checks if a suffix of the harmony class `suffix_harmony` can attach to `stem`.
+pub fn is_harmonic(stem: &str, suffix_harmony: Harmony) -> bool {
+    let stem_harmony = vowel_harmony(stem);
+    match suffix_harmony {
+        Harmony::Neutral => true,
+        Harmony::Masculine => stem_harmony != Harmony::Feminine,
+        Harmony::Feminine => stem_harmony == Harmony::Feminine || stem_harmony == Harmony::Neutral,
+        Harmony::Rounded => stem_harmony == Harmony::Rounded,
+    }
+}
+
+/// Checks if a romanized token has both masculine and feminine vowels,
+/// which rarely happens in a native stem.
+pub fn has_mixed_harmony(token: &str) -> bool {
+    let is_masculine = token.contains('a') || token.contains('ū');
+    is_masculine && token.contains('e')
+}
+
+/// Checks if a romanized token ends with a vowel.
+pub fn ends_with_vowel(token: &str) -> bool {
+    matches!(
+        token.chars().last(),
+        Some('a' | 'e' | 'i' | 'o' | 'u' | 'ū' | 'v')
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("aku\u{304}"), "akū");
+        assert_eq!(normalize("akū"), "akū");
+        assert_eq!(normalize("U\u{304}lgiyan"), "Ūlgiyan");
+        assert_eq!(normalize("s\u{30c}olo"), "šolo");
+        assert_eq!(normalize("cooha"), "cooha");
+    }
+}
@@ -0,0 +1,137 @@
+use std::{str::FromStr, sync::LazyLock};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::function_word::FunctionWord;
+use crate::word::{Case, Detail, Number, PartOfSpeech, Person, Word};
+
+/// case form of a personal or demonstrative pronoun
+///
+/// Pronouns are declined suppletively, e.g. "mini" is the genitive of "bi",
+/// so their case forms cannot be split into a base and a clitic.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PronounForm {
+    /// surface form, e.g. "mimbe"
+    pub surface: String,
+    /// nominative form of the pronoun, e.g. "bi"
+    pub lemma: String,
+    pub person: Person,
+    pub number: Number,
+    #[serde(deserialize_with = "deserialize_case")]
+    pub case: Case,
+}
+
+/// Deserialize a case written in snake case as in `function_word.json`, e.g. "genitive".
+fn deserialize_case<'de, D>(deserializer: D) -> Result<Case, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let case = String::deserialize(deserializer)?;
+    Case::from_str(&case).map_err(serde::de::Error::custom)
+}
+
+impl PronounForm {
+    /// Returns the emission cost of the pronoun form.
+    ///
+    /// An entry of the lexicon is more reliable than a segmentation by suffixes,
+    /// but a form which is also a function word, e.g. "i" or "be",
+    /// costs as much as the function word.
+    fn emission_cost(&self) -> isize {
+        if FunctionWord::from_str(&self.surface).is_ok() {
+            -1
+        } else {
+            -10
+        }
+    }
+}
+
+impl From<PronounForm> for Word {
+    fn from(pronoun_form: PronounForm) -> Self {
+        let emission_cost = pronoun_form.emission_cost();
+        Word {
+            base: pronoun_form.lemma,
+            suffixes: None,
+            part_of_speech: PartOfSpeech::Pronoun,
+            detail: Some(Detail::Case(pronoun_form.case)),
+            emission_cost,
+            number: Some(pronoun_form.number),
+            person: Some(pronoun_form.person),
+            ..Default::default()
+        }
+    }
+}
+
+/// Find the pronoun forms whose surface form is `token`.
+pub fn find_pronoun_forms(token: &str) -> Vec<PronounForm> {
+    get_pronoun_form_list()
+        .iter()
+        .filter(|pronoun_form| pronoun_form.surface == token)
+        .cloned()
+        .collect()
+}
+
+pub fn get_pronoun_form_list() -> &'static [PronounForm] {
+    &PRONOUN_FORM_LIST
+}
+
+/// pronoun forms read once since they are looked up for every token
+static PRONOUN_FORM_LIST: LazyLock<Vec<PronounForm>> = LazyLock::new(|| {
+    let data = include_str!("../resources/pronoun.json");
+    serde_json::from_str(data).expect("JSON was not well-formatted")
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find_pronoun_form(token: &str) -> PronounForm {
+        let pronoun_forms = find_pronoun_forms(token);
+        assert_eq!(pronoun_forms.len(), 1, "{}", token);
+        pronoun_forms[0].clone()
+    }
+
+    #[test]
+    fn test_find_pronoun_forms() {
+        let pronoun_form = find_pronoun_form("mini");
+        assert_eq!(pronoun_form.lemma, "bi");
+        assert_eq!(pronoun_form.person, Person::First);
+        assert_eq!(pronoun_form.number, Number::Singular);
+        assert_eq!(pronoun_form.case, Case::Genitive);
+
+        let pronoun_form = find_pronoun_form("simbe");
+        assert_eq!(pronoun_form.lemma, "si");
+        assert_eq!(pronoun_form.person, Person::Second);
+        assert_eq!(pronoun_form.case, Case::Accusative);
+
+        let pronoun_form = find_pronoun_form("museci");
+        assert_eq!(pronoun_form.lemma, "muse");
+        assert_eq!(pronoun_form.number, Number::Plural);
+        assert_eq!(pronoun_form.case, Case::Ablative);
+
+        // Demonstratives are third person.
+        let pronoun_form = find_pronoun_form("tesede");
+        assert_eq!(pronoun_form.lemma, "tese");
+        assert_eq!(pronoun_form.person, Person::Third);
+        assert_eq!(pronoun_form.number, Number::Plural);
+        assert_eq!(pronoun_form.case, Case::DativeLocative);
+
+        assert!(find_pronoun_forms("hafan").is_empty());
+    }
+
+    #[test]
+    fn test_pronoun_word() {
+        let word = Word::from(find_pronoun_form("suweni"));
+        assert_eq!(word.base, "suwe");
+        assert_eq!(word.part_of_speech, PartOfSpeech::Pronoun);
+        assert_eq!(word.detail, Some(Detail::Case(Case::Genitive)));
+        assert_eq!(word.person, Some(Person::Second));
+        assert_eq!(word.number, Some(Number::Plural));
+        assert_eq!(word.emission_cost, -10);
+
+        // "be" is also the accusative clitic and costs as much as the function word.
+        let word = Word::from(find_pronoun_form("be"));
+        assert_eq!(word.number, Some(Number::Plural));
+        assert_eq!(word.detail, Some(Detail::Case(Case::Nominative)));
+        assert_eq!(word.emission_cost, -1);
+    }
+}
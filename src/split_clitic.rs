@@ -1,11 +1,14 @@
-use std::str::FromStr;
+use std::{str::FromStr, sync::LazyLock};
 
-use crate::function_word::{get_function_word_list, FunctionWord};
+use crate::function_word::{get_function_word_list, CliticCondition, FunctionWord};
+use crate::phoneme::normalize;
 use crate::word::{Case, Detail, PartOfSpeech, Word};
 
+#[derive(Clone)]
 struct CaseClitic {
     entry: String,
     cases: Vec<Case>,
+    condition: CliticCondition,
 }
 
 impl TryFrom<FunctionWord> for CaseClitic {
@@ -27,57 +30,192 @@ impl TryFrom<FunctionWord> for CaseClitic {
             Ok(cases) => Ok(CaseClitic {
                 entry: function_word.entry,
                 cases,
+                condition: function_word.condition,
             }),
             Err(_) => Err("Invalid case".into()),
         }
     }
 }
 
-impl Into<Vec<Word>> for CaseClitic {
-    fn into(self) -> Vec<Word> {
-        let words = self
+impl From<CaseClitic> for Vec<Word> {
+    fn from(case_clitic: CaseClitic) -> Self {
+        let words = case_clitic
             .cases
             .iter()
             .map(|case| Word {
-                base: self.entry.clone(),
+                base: case_clitic.entry.clone(),
                 suffixes: None,
                 part_of_speech: PartOfSpeech::Clitic,
                 detail: Some(Detail::Case(case.clone())),
                 emission_cost: -1,
+                ..Default::default()
             })
             .collect();
         words
     }
 }
 
+/// case clitics extracted once from the function words
+static CASE_CLITIC_LIST: LazyLock<Vec<CaseClitic>> = LazyLock::new(|| {
+    get_function_word_list()
+        .iter()
+        .filter_map(|function_word| CaseClitic::try_from(function_word.clone()).ok())
+        .collect()
+});
+
+/// split of a word into a base and clitics
+#[derive(Clone, Debug)]
+pub struct CliticSplit {
+    /// base before the clitics
+    ///
+    /// The base is not be fully split into a word and suffixes.
+    pub base: String,
+    /// clitics after the base from the left to the right
+    ///
+    /// A case clitic may be followed by a particle.
+    /// For example, "niyalmaini" is split into "niyalma" and `vec!["i", "ni"]`.
+    pub clitics: Vec<Word>,
+}
+
+/// Split a word into a base and clitics in every possible way.
+///
+/// A split has a case clitic, optionally followed by a particle.
+/// If a clitic has some cases, a split is returned for each of them.
+///
+/// Returns Err if the word is empty, consists entirely of whitespace or has no clitic.
+pub fn split_word_into_word_clitics(word: &str) -> Result<Vec<CliticSplit>, String> {
+    if word.is_empty() {
+        return Err("Empty string".into());
+    }
+    if word.chars().all(|c| c.is_whitespace()) {
+        return Err("Whitespace string".into());
+    }
+    let case_clitics = &CASE_CLITIC_LIST;
+    let word = normalize(word);
+    let word = word.as_str();
+
+    // a case clitic at the end of the word
+    let mut clitic_splits = split_case_clitics(word, case_clitics, &[]);
+    // a case clitic followed by a particle
+    for function_word in get_function_word_list() {
+        if function_word.part_of_speech != PartOfSpeech::Particle {
+            continue;
+        }
+        if let Some(rest) = word.strip_suffix(function_word.entry.as_str()) {
+            let particle_words: Vec<Word> = function_word.clone().into();
+            for particle_word in particle_words {
+                clitic_splits.extend(split_case_clitics(rest, case_clitics, &[particle_word]));
+            }
+        }
+    }
+
+    if clitic_splits.is_empty() {
+        return Err("Cannot find a clitic".into());
+    }
+    Ok(clitic_splits)
+}
+
 /// Split a word into a word and a clitic and return (word, clitic)
 ///
-/// The word is not be fully split into a word and suffixes;
-/// the field `suffixes` of the returned `Word` is `None`.
+/// Only the first clitic in `function_word.json` matching the end of the word is tried,
+/// and a word for each of its cases is returned.
+#[deprecated(note = "use `split_word_into_word_clitics` to get every split")]
 pub fn split_word_into_word_clitic(word: &str) -> Result<(String, Vec<Word>), String> {
-    let function_words = get_function_word_list();
+    let clitic_splits = split_word_into_word_clitics(word)?;
+    let base = clitic_splits[0].base.clone();
+    let case_clitic_words = clitic_splits
+        .into_iter()
+        .filter(|clitic_split| clitic_split.base == base && clitic_split.clitics.len() == 1)
+        .flat_map(|clitic_split| clitic_split.clitics)
+        .collect();
+    Ok((base, case_clitic_words))
+}
+
+/// Split a word into a base and a sentence particle attached to it in every possible way,
+/// e.g. "biheni" into "bihe" and "ni".
+///
+/// If a particle has some details, a split is returned for each of them.
+///
+/// Returns Err if the word is empty, consists entirely of whitespace or has no particle.
+pub fn split_word_into_word_particles(word: &str) -> Result<Vec<CliticSplit>, String> {
     if word.is_empty() {
         return Err("Empty string".into());
     }
     if word.chars().all(|c| c.is_whitespace()) {
         return Err("Whitespace string".into());
     }
-    for function_word in function_words.iter() {
-        let Ok(case_clitic) = CaseClitic::try_from(function_word.clone()) else {
+    let word = normalize(word);
+    let mut particle_splits = vec![];
+    for function_word in get_function_word_list() {
+        if function_word.part_of_speech != PartOfSpeech::Particle {
+            continue;
+        }
+        let Some(base) = word.strip_suffix(function_word.entry.as_str()) else {
             continue;
         };
+        if base.is_empty() {
+            continue;
+        }
+        let particle_words: Vec<Word> = function_word.clone().into();
+        for particle_word in particle_words {
+            particle_splits.push(CliticSplit {
+                base: base.to_string(),
+                clitics: vec![particle_word],
+            });
+        }
+    }
 
-        let clitic_entry = case_clitic.entry.as_str();
-        if word.ends_with(clitic_entry) {
-            let base = word[..word.len() - clitic_entry.len()].to_string();
-            if base.is_empty() {
-                return Err("Empty base".into());
-            }
-            let case_clitic_words: Vec<Word> = case_clitic.into();
-            return Ok((base, case_clitic_words));
+    if particle_splits.is_empty() {
+        return Err("Cannot find a particle".into());
+    }
+    Ok(particle_splits)
+}
+
+/// Split off a case clitic from the end of a word in every possible way.
+///
+/// * `word` - A word to split.
+/// * `right_clitics` - Clitics already split off from the right of the word.
+fn split_case_clitics(
+    word: &str,
+    case_clitics: &[CaseClitic],
+    right_clitics: &[Word],
+) -> Vec<CliticSplit> {
+    let mut clitic_splits = vec![];
+    for case_clitic in case_clitics.iter() {
+        let Some(base) = word.strip_suffix(case_clitic.entry.as_str()) else {
+            continue;
+        };
+        if base.is_empty() || !case_clitic.condition.is_satisfied_by(base) {
+            continue;
+        }
+        let case_clitic_words: Vec<Word> = case_clitic.clone().into();
+        for case_clitic_word in case_clitic_words {
+            let mut clitics = vec![case_clitic_word];
+            clitics.extend(right_clitics.iter().cloned());
+            clitic_splits.push(CliticSplit {
+                base: base.to_string(),
+                clitics,
+            });
         }
     }
-    Err("Cannot find a clitic".into())
+    clitic_splits
+}
+
+/// Returns the clitic marking `case` after `noun`.
+///
+/// If the case has some allomorphs, the one whose condition the noun satisfies is chosen;
+/// e.g. genitive "i" after "niyalma" and "ni" after "wang".
+/// If some clitics mark the case, the first listed one is chosen.
+///
+/// Returns Err if no clitic marks the case after the noun.
+pub fn choose_case_clitic(noun: &str, case: &Case) -> Result<String, String> {
+    CASE_CLITIC_LIST
+        .iter()
+        .find(|case_clitic| {
+            case_clitic.cases.contains(case) && case_clitic.condition.is_satisfied_by(noun)
+        })
+        .map(|case_clitic| case_clitic.entry.clone())
+        .ok_or_else(|| format!("Cannot find a clitic for {} after \"{}\"", case, noun))
 }
 
 #[cfg(test)]
@@ -85,10 +223,66 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(deprecated)]
     fn test_split_word_into_word_clitic() {
+        let (base, clitics) = split_word_into_word_clitic("niyalmai").unwrap();
+        assert_eq!(base, "niyalma");
+        assert_eq!(clitics.len(), 2);
+        assert!(clitics.iter().all(|clitic| clitic.base == "i"));
+    }
+
+    #[test]
+    fn test_split_word_into_word_clitics() {
         let word = "niyalmai";
-        let (word_entry, case_clitic) = split_word_into_word_clitic(word).unwrap();
-        assert_eq!(word_entry, "niyalma");
-        assert_eq!(case_clitic[0].base, "i");
+        let clitic_splits = split_word_into_word_clitics(word).unwrap();
+        assert_eq!(clitic_splits[0].base, "niyalma");
+        assert_eq!(clitic_splits[0].clitics[0].base, "i");
+
+        // Both "deri" and "i" match the end of "bigaderi".
+        let clitic_splits = split_word_into_word_clitics("bigaderi").unwrap();
+        let prolative = clitic_splits
+            .iter()
+            .find(|clitic_split| clitic_split.base == "biga")
+            .unwrap();
+        assert_eq!(
+            prolative.clitics[0].detail,
+            Some(Detail::Case(Case::Prolative))
+        );
+
+        // a particle after a case clitic
+        let clitic_splits = split_word_into_word_clitics("niyalmaini").unwrap();
+        let stacked = clitic_splits
+            .iter()
+            .find(|clitic_split| clitic_split.base == "niyalma")
+            .unwrap();
+        assert_eq!(stacked.clitics.len(), 2);
+        assert_eq!(stacked.clitics[1].part_of_speech, PartOfSpeech::Particle);
+
+        // "ni" only attaches after "ng" and "i" never does.
+        let clitic_splits = split_word_into_word_clitics("wangni").unwrap();
+        assert!(clitic_splits
+            .iter()
+            .any(|clitic_split| clitic_split.base == "wang"));
+        assert!(split_word_into_word_clitics("niyalmani")
+            .unwrap()
+            .iter()
+            .all(|clitic_split| clitic_split.base != "niyalma"));
+        assert!(split_word_into_word_clitics("wangi").is_err());
+    }
+
+    #[test]
+    fn test_split_word_into_word_particles() {
+        let particle_splits = split_word_into_word_particles("genehekai").unwrap();
+        assert_eq!(particle_splits[0].base, "genehe");
+        assert_eq!(particle_splits[0].clitics[0].base, "kai");
+        assert!(split_word_into_word_particles("kai").is_err());
+    }
+
+    #[test]
+    fn test_choose_case_clitic() {
+        assert_eq!(choose_case_clitic("niyalma", &Case::Genitive).unwrap(), "i");
+        assert_eq!(choose_case_clitic("wang", &Case::Genitive).unwrap(), "ni");
+        assert_eq!(choose_case_clitic("wang", &Case::Accusative).unwrap(), "be");
+        assert!(choose_case_clitic("wang", &Case::Vocative).is_err());
     }
 }
@@ -1,87 +1,322 @@
-use core::panic;
+use std::sync::LazyLock;
 
 use crate::{
-    phoneme::is_valid_structure,
-    word::{Detail, PartOfSpeech, Suffix, Word},
+    morphotactics::Morphotactics,
+    phoneme::{ends_with_vowel, is_valid_structure, normalize},
+    syllable::syllabify,
+    word::{Alternation, Conjugation, Detail, PartOfSpeech, Suffix, Word},
 };
 
+/// rule of an alternation at a morpheme boundary
+///
+/// A rule applies to surface forms of some suffixes
+/// and the base before them must satisfy the condition of the rule.
+struct AlternationRule {
+    alternation: Alternation,
+    /// Checks if the rule applies to the suffix.
+    applies_to: fn(&Suffix) -> bool,
+    /// Checks if the base before the surface form satisfies the condition of the rule.
+    condition: fn(&str) -> bool,
+}
+
+const ALTERNATION_RULES: [AlternationRule; 3] = [
+    AlternationRule {
+        alternation: Alternation::VelarPerfective,
+        applies_to: |suffix| {
+            suffix.suffix.starts_with('k')
+                && (suffix.conjugation == Conjugation::PerfectiveParticiple
+                    || suffix.conjugation == Conjugation::PerfectiveFinite)
+        },
+        condition: takes_velar_perfective,
+    },
+    AlternationRule {
+        alternation: Alternation::LabialConverb,
+        applies_to: |suffix| suffix.suffix == "mpi",
+        condition: ends_with_vowel,
+    },
+    AlternationRule {
+        alternation: Alternation::LabialConverb,
+        applies_to: |suffix| suffix.suffix == "pi",
+        condition: |base| base.ends_with('m'),
+    },
+];
+
+/// Checks if a verb stem takes "ka", "ke" or "ko" instead of "ha", "he" or "ho".
+///
+/// They are monosyllabic stems such as "je" and "te",
+/// and stems ending with "si", "ci", "te" or "he" such as "dosi", "tuci", "ete" and "tuhe".
+fn takes_velar_perfective(base: &str) -> bool {
+    // The end is checked first since syllabification is more expensive.
+    ["si", "ci", "te", "he"]
+        .iter()
+        .any(|end| base.ends_with(end))
+        || syllabify(base).is_ok_and(|syllables| syllables.len() == 1)
+}
+
+/// additional emission cost of a stem whose final n is recovered
+///
+/// The recovered stem costs more than the surface one
+/// even if the unknown word model prefers a noun ending with n,
+/// so that it does not double the best analyses of the token.
+const FINAL_NASAL_LOSS_COST: isize = 3;
+
+/// Returns the alternation of `suffix` after `base`.
+///
+/// Returns Err if a rule applies to the suffix but the base does not satisfy its condition.
+fn find_alternation(suffix: &Suffix, base: &str) -> Result<Option<Alternation>, String> {
+    match ALTERNATION_RULES
+        .iter()
+        .find(|rule| (rule.applies_to)(suffix))
+    {
+        Some(rule) if (rule.condition)(base) => Ok(Some(rule.alternation)),
+        Some(rule) => Err(format!(
+            "\"{}\" does not satisfy {}",
+            base, rule.alternation
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Recover the final n of a noun dropped before a suffix or a clitic.
+///
+/// Returns None if the base does not end with a vowel
+/// or the recovered stem is not a valid phoneme structure.
+///
+/// For example, "hafa" of "hafasa" is recovered to "hafan".
+pub fn recover_final_nasal(base: &str) -> Option<String> {
+    if !ends_with_vowel(base) {
+        return None;
+    }
+    let stem = format!("{}n", base);
+    if is_valid_structure(&stem) {
+        Some(stem)
+    } else {
+        None
+    }
+}
+
 /// Spilt a word into a suffix and its base.
 ///
+/// If several suffixes match the end of the word, the first one in `suffix.csv` is used.
+/// Use `split_word_into_suffix_bases` to get all of them.
+///
 /// Returns Err if the word is empty or consists entirely of whitespace.
 ///
 /// * `word` - A word to split.
 pub fn split_word_into_suffix_base(word: &str) -> Result<Word, String> {
-    let suffixes = read_suffix_csv();
+    let split_words = split_word_into_suffix_bases(word)?;
+    match split_words.into_iter().next() {
+        Some(split_word) => Ok(split_word),
+        None => Ok(Word::new(word.to_string(), None, PartOfSpeech::Noun, None)),
+    }
+}
+
+/// Spilt a word into a suffix and its base in every possible way.
+///
+/// Each returned `Word` has exactly one suffix.
+/// The result is empty if no suffix matches the end of the word.
+///
+/// Returns Err if the word is empty or consists entirely of whitespace.
+///
+/// * `word` - A word to split.
+pub fn split_word_into_suffix_bases(word: &str) -> Result<Vec<Word>, String> {
+    split_word_into_suffix_bases_before(&normalize(word), None)
+}
+
+/// Spilt a word into a suffix and its base in every possible way
+/// where the suffix can be followed by `right_suffix` according to the morphotactics.
+///
+/// * `word` - A word to split.
+/// * `right_suffix` - A suffix already split off from the right of the word
+///   and adjacent to the new suffix, i.e. the innermost one split off so far.
+fn split_word_into_suffix_bases_before(
+    word: &str,
+    right_suffix: Option<&Suffix>,
+) -> Result<Vec<Word>, String> {
+    let suffixes = &SUFFIX_LIST;
+    let morphotactics = Morphotactics::load();
     if word.is_empty() {
         return Err("Empty string".into());
     }
     if word.chars().all(|c| c.is_whitespace()) {
         return Err("Whitespace string".into());
     }
+    let mut split_words = vec![];
     for suffix in suffixes.iter() {
         let suffix_entry = suffix.suffix.as_str();
-        if word.ends_with(suffix_entry) {
-            let base = word[..word.len() - suffix_entry.len()].to_string();
+        if let Some(base) = word.strip_suffix(suffix_entry) {
+            let base = base.to_string();
             // Skip if the base is not a valid phoneme structure.
             if !is_valid_structure(&base) {
                 continue;
             }
+            // Skip if the suffix disagrees with the base in vowel harmony or the stem constraint.
+            if !suffix.can_attach_to(&base) {
+                continue;
+            }
+            // Skip if the suffix cannot precede the suffix on its right
+            // or cannot end the word if it is the outermost one.
+            match right_suffix {
+                Some(right_suffix) if !morphotactics.allows(suffix, right_suffix) => continue,
+                None if !morphotactics.can_end_word(suffix) => continue,
+                _ => {}
+            }
+            // Skip if the surface form of the suffix cannot appear after the base.
+            let Ok(alternation) = find_alternation(suffix, &base) else {
+                continue;
+            };
 
             let suffixes = vec![suffix.clone()];
-            let split_word = Word::new(
+            let mut split_word = Word::new(
                 base,
                 Some(suffixes),
                 suffix.part_of_speech,
                 Some(Detail::Conjugation(suffix.conjugation)),
             );
-            return Ok(split_word);
+            split_word.alternations.extend(alternation);
+            split_words.push(split_word);
         }
     }
-    Ok(Word {
-        base: word.to_string(),
-        suffixes: None,
-        part_of_speech: PartOfSpeech::Noun,
-        detail: None,
-        emission_cost: 0,
-    })
+    Ok(split_words)
 }
 
 /// Generate all possible segmentations of a word.
+///
+/// Every matching suffix is explored at every depth,
+/// so the result contains all segmentation paths of the word;
+/// the first one is the word itself without any suffix.
+/// Suffix chains which are not allowed by the morphotactics are pruned.
+///
+/// If a suffix attaching to a noun or an adjective follows a vowel,
+/// the segmentation with the final n of the base recovered is also generated.
+pub fn generate_segmentations(token: &str) -> Vec<Word> {
+    let word = Word::new(normalize(token), None, PartOfSpeech::Noun, None);
+    let mut words = vec![word.clone()];
+    extend_segmentations(&word, &mut words);
+    words
+}
+
+/// Generate all possible segmentations of a word and append them to `words`.
+///
+/// The word itself without any suffix is appended only if `words` is empty.
+#[deprecated(note = "use `generate_segmentations`, which returns every segmentation of the token")]
 pub fn generate_all_segmentations(token: &str, mut words: Vec<Word>) -> Vec<Word> {
-    // First, add the original word to the list of words.
-    if words.len() == 0 {
-        words.push(Word {
-            base: token.to_string(),
-            suffixes: None,
-            part_of_speech: PartOfSpeech::Noun,
-            detail: None,
-            emission_cost: 0,
-        });
-    }
-    let segmented_word = split_word_into_suffix_base(token).expect("Error splitting word");
-    match segmented_word.suffixes {
-        Some(suffixes) => {
-            let mut suffixes = suffixes.clone();
-            let previous_suffix = words[words.len() - 1].suffixes.clone().unwrap_or(vec![]);
-            suffixes.extend(previous_suffix);
-            let conjugation = suffixes.last().unwrap().conjugation.clone();
-            let part_of_speech = suffixes.last().unwrap().part_of_speech.clone();
-
-            let segmented_word = Word::new(
-                segmented_word.base,
-                Some(suffixes),
-                part_of_speech,
-                Some(Detail::Conjugation(conjugation)),
-            );
+    let segmentations = generate_segmentations(token);
+    let skip = usize::from(!words.is_empty());
+    words.extend(segmentations.into_iter().skip(skip));
+    words
+}
+
+/// Generate all possible segmentations of a word before a clitic.
+///
+/// In addition to `generate_segmentations`,
+/// the word with the final n recovered is generated
+/// because the final n of a noun is dropped before a clitic.
+pub fn generate_segmentations_before_clitic(token: &str) -> Vec<Word> {
+    let token = normalize(token);
+    let mut words = generate_segmentations(&token);
+    if let Some(stem) = recover_final_nasal(&token) {
+        let mut word = Word::new(stem, None, PartOfSpeech::Noun, None);
+        word.alternations.push(Alternation::FinalNasalLoss);
+        word.emission_cost += FINAL_NASAL_LOSS_COST;
+        words.push(word);
+    }
+    words
+}
+
+/// Analyze an unsuffixed token as the imperative of a verb.
+///
+/// The bare stem of a verb is the imperative, e.g. "gene" or "tuwa".
+/// Returns None unless the token is a known verb stem in `verb_stem.json`
+/// or a plausible one, which has a valid structure, ends with a vowel
+/// and is more likely a verb than a noun.
+///
+/// * `token` - An unsuffixed token.
+/// * `verb_noun_cost` - The stem cost of the token as a verb minus that as a noun,
+///   e.g. by the unknown word model; a negative value means that it is more likely a verb.
+pub fn analyze_bare_imperative(token: &str, verb_noun_cost: isize) -> Option<Word> {
+    let is_plausible_verb_stem =
+        is_valid_structure(token) && ends_with_vowel(token) && verb_noun_cost < 0;
+    if !is_known_verb_stem(token) && !is_plausible_verb_stem {
+        return None;
+    }
+    Some(Word::new(
+        token.to_string(),
+        None,
+        PartOfSpeech::Verb,
+        Some(Detail::Conjugation(Conjugation::Imperative)),
+    ))
+}
 
-            words.push(segmented_word.clone());
-            let base = segmented_word.base;
-            generate_all_segmentations(&base, words)
+/// Split off suffixes from the base of `word` recursively and push every segmentation to `words`.
+///
+/// * `word` - A word whose base is split.
+///   Its suffixes have already been split off from the right of the base.
+fn extend_segmentations(word: &Word, words: &mut Vec<Word>) {
+    let right_suffixes = word.suffixes.clone().unwrap_or_default();
+    // The order of suffixes is from the right to the left,
+    // so the last one is adjacent to the suffix split off next.
+    let adjacent_suffix = right_suffixes.last();
+    let Ok(split_words) = split_word_into_suffix_bases_before(&word.base, adjacent_suffix) else {
+        return;
+    };
+    for split_word in split_words {
+        // The order of suffixes is from the right to the left,
+        // so the new suffix is the innermost one.
+        let mut suffixes = right_suffixes.clone();
+        suffixes.extend(split_word.suffixes.unwrap_or_default());
+        let conjugation = suffixes.first().unwrap().conjugation;
+        let part_of_speech = suffixes.last().unwrap().part_of_speech;
+
+        let mut segmented_word = Word::new(
+            split_word.base.clone(),
+            Some(suffixes.clone()),
+            part_of_speech,
+            Some(Detail::Conjugation(conjugation)),
+        );
+        segmented_word.alternations = word.alternations.clone();
+        segmented_word
+            .alternations
+            .extend(split_word.alternations.iter().copied());
+        words.push(segmented_word.clone());
+
+        // The final n of a noun is dropped before a suffix.
+        let attaches_to_nominal =
+            matches!(part_of_speech, PartOfSpeech::Noun | PartOfSpeech::Adjective);
+        if attaches_to_nominal {
+            if let Some(stem) = recover_final_nasal(&segmented_word.base) {
+                let mut recovered_word = segmented_word.clone();
+                recovered_word.base = stem;
+                recovered_word
+                    .alternations
+                    .push(Alternation::FinalNasalLoss);
+                recovered_word.emission_cost += FINAL_NASAL_LOSS_COST;
+                words.push(recovered_word);
+            }
         }
-        None => words,
+
+        // A known verb stem is not split further, e.g. "gene" in "genehe" is not "ge" and "ne".
+        if is_known_verb_stem(&segmented_word.base) {
+            continue;
+        }
+        extend_segmentations(&segmented_word, words);
     }
 }
 
+/// Checks if a token is in the lexicon of verb stems.
+fn is_known_verb_stem(token: &str) -> bool {
+    VERB_STEM_LIST.iter().any(|stem| stem == token)
+}
+
+/// verb stems read once since every token is looked up
+static VERB_STEM_LIST: LazyLock<Vec<String>> = LazyLock::new(|| {
+    let data = include_str!("../resources/verb_stem.json");
+    serde_json::from_str(data).expect("JSON was not well-formatted")
+});
+
+/// suffixes read once since they are matched at every split of a word
+static SUFFIX_LIST: LazyLock<Vec<Suffix>> = LazyLock::new(read_suffix_csv);
+
 fn read_suffix_csv() -> Vec<Suffix> {
     let csv = include_str!("../resources/suffix.csv");
     let mut rdr = csv::Reader::from_reader(csv.as_bytes());
@@ -100,6 +335,9 @@ fn read_suffix_csv() -> Vec<Suffix> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::word::{
+        Alternation, Aspect, Conjugation, Mood, Number, Tense, VerbForm, VerbMood, Voice,
+    };
 
     #[test]
     fn it_works() {
@@ -114,7 +352,7 @@ mod tests {
                 assert_eq!(split_word.base, expected_base);
                 assert_eq!(suffix[0].suffix, expected_suffix);
             }
-            Err(_) => assert!(false),
+            Err(_) => panic!(),
         }
 
         let whitespace = split_word_into_suffix_base("   ");
@@ -124,12 +362,263 @@ mod tests {
         assert!(empty.is_err());
     }
 
+    #[test]
+    fn test_morphotactics_of_segmentations() {
+        // Each suffix is checked against the adjacent one,
+        // so every segmentation is a well-formed chain.
+        let morphotactics = Morphotactics::load();
+        for token in ["tuwabunumbi", "tuwabuhao", "arambihao", "ulhicungga"] {
+            for word in generate_segmentations(token) {
+                let suffixes = word.suffixes.unwrap_or_default();
+                assert!(morphotactics.allows_chain(&suffixes), "{}", token);
+            }
+        }
+    }
+
+    #[test]
+    fn test_three_suffix_chain() {
+        // "bu" is followed by the participle "ha" and not by the particle "o",
+        // so "tuwa" is reached only if "bu" is checked against "ha".
+        let valid_word = generate_segmentations("tuwabuhao");
+        let bases: Vec<&str> = valid_word.iter().map(|word| word.base.as_str()).collect();
+        assert_eq!(bases, vec!["tuwabuhao", "tuwabuha", "tuwabu", "tuwa"]);
+        let suffixes: Vec<&str> = valid_word[3]
+            .suffixes
+            .iter()
+            .flatten()
+            .map(|suffix| suffix.suffix.as_str())
+            .collect();
+        assert_eq!(suffixes, vec!["o", "ha", "bu"]);
+
+        // The finite "mbi" is followed by the particle "o" but not by the participle "ha".
+        let valid_word = generate_segmentations("arambihao");
+        assert!(valid_word.iter().all(|word| word.base != "ara"));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_generate_all_segmentations() {
+        let words = generate_all_segmentations("tuwabumbi", vec![]);
+        assert_eq!(words.len(), generate_segmentations("tuwabumbi").len());
+        assert!(words[0].suffixes.is_none());
+    }
+
     #[test]
     fn test_generate_all_segmentations() {
-        let valid_word = generate_all_segmentations("tuwabumbi", vec![]);
+        let valid_word = generate_segmentations("tuwabumbi");
         assert_eq!(valid_word.len(), 3);
         assert_eq!(valid_word[0].base, "tuwabumbi");
         assert_eq!(valid_word[1].base, "tuwabu");
         assert_eq!(valid_word[2].base, "tuwa");
+        let suffixes = valid_word[2].suffixes.as_ref().unwrap();
+        assert_eq!(suffixes[0].suffix, "mbi");
+        assert_eq!(suffixes[1].suffix, "bu");
+
+        // "mbi" cannot be followed by "ha".
+        let valid_word = generate_segmentations("arambiha");
+        assert!(valid_word.iter().all(|word| word.base != "ara"));
+
+        // Both "mbihe" and "he" match the end of "tembihe".
+        // Before the harmony was checked, this was tested with "arambihe",
+        // but the feminine "he" cannot attach to the masculine "arambi".
+        let valid_word = generate_segmentations("tembihe");
+        assert_eq!(valid_word.len(), 3);
+        assert!(valid_word.iter().any(|word| word.base == "te"));
+        assert!(valid_word.iter().any(|word| word.base == "tembi"));
+
+        // The feminine "he" does not attach to the masculine "arambi".
+        let valid_word = generate_segmentations("arambihe");
+        assert!(valid_word.iter().all(|word| word.base != "arambi"));
+    }
+
+    #[test]
+    fn test_derivation() {
+        // "ulhicun" is a noun derived from the verb "ulhi".
+        let valid_word = generate_segmentations("ulhicun");
+        let derived = valid_word.iter().find(|word| word.base == "ulhi").unwrap();
+        assert_eq!(derived.part_of_speech, PartOfSpeech::Verb);
+        assert_eq!(derived.derived_part_of_speech(), PartOfSpeech::Noun);
+
+        // "ambakan" is the adjective "amba" with the diminutive "kan".
+        let valid_word = generate_segmentations("ambakan");
+        let derived = valid_word.iter().find(|word| word.base == "amba").unwrap();
+        assert_eq!(derived.derived_part_of_speech(), PartOfSpeech::Adjective);
+    }
+
+    #[test]
+    fn test_denominal_adjective_harmony() {
+        // "ngga", "ngge" and "nggo" agree with the vowels of the noun.
+        let has_split = |token: &str, base: &str| {
+            generate_segmentations(token)
+                .iter()
+                .any(|word| word.base == base && word.suffixes.is_some())
+        };
+        assert!(has_split("aisingga", "aisin"));
+        assert!(has_split("erdemungge", "erdemu"));
+        assert!(has_split("doronggo", "doro"));
+        assert!(!has_split("aisingge", "aisin"));
+        assert!(!has_split("dorongge", "doro"));
+        assert!(!has_split("erdemungga", "erdemu"));
+    }
+
+    #[test]
+    fn test_verbal_derivation() {
+        // "tuwanambi" is "tuwa" with the centrifugal "na" and "mbi".
+        let valid_word = generate_segmentations("tuwanambi");
+        let root = valid_word.iter().find(|word| word.base == "tuwa").unwrap();
+        let suffixes = root.suffixes.as_ref().unwrap();
+        assert_eq!(suffixes[0].suffix, "mbi");
+        assert_eq!(suffixes[1].conjugation, Conjugation::CentrifugalVerbal);
+
+        // A verb derivation does not end a word, so "gene" is not "ge" and "ne".
+        let valid_word = generate_segmentations("gene");
+        assert!(valid_word.iter().all(|word| word.suffixes.is_none()));
+        // The known stem "gene" is not split further in "genehe".
+        let valid_word = generate_segmentations("genehe");
+        assert!(valid_word.iter().any(|word| word.base == "gene"));
+        assert!(valid_word.iter().all(|word| word.base != "ge"));
+
+        let valid_word = generate_segmentations("acanambi");
+        assert!(valid_word.iter().any(|word| word.base == "aca"));
+
+        let valid_word = generate_segmentations("gisurendumbi");
+        let root = valid_word
+            .iter()
+            .find(|word| word.base == "gisure")
+            .unwrap();
+        let suffixes = root.suffixes.as_ref().unwrap();
+        assert_eq!(suffixes[1].conjugation, Conjugation::CooperativeVerbal);
+
+        // "weilembi" is the verb "weile" with "mbi".
+        let valid_word = generate_segmentations("weilembi");
+        let split_word = valid_word.iter().find(|word| word.base == "weile").unwrap();
+        assert_eq!(split_word.suffixes.as_ref().unwrap().len(), 1);
+
+        // "le" makes a verb from a noun, so it can be followed by "mbi".
+        // "ejelembi" is derived from "ejen" whose final n is dropped before "le".
+        let valid_word = generate_segmentations("ejelembi");
+        let root = valid_word.iter().find(|word| word.base == "ejen").unwrap();
+        assert_eq!(root.part_of_speech, PartOfSpeech::Noun);
+        assert_eq!(root.derived_part_of_speech(), PartOfSpeech::Verb);
+        let suffixes = root.suffixes.as_ref().unwrap();
+        assert_eq!(suffixes[1].conjugation, Conjugation::DenominalVerbal);
+    }
+
+    #[test]
+    fn test_alternation() {
+        // "hafasa" is the plural of "hafan".
+        let valid_word = generate_segmentations("hafasa");
+        let recovered = valid_word.iter().find(|word| word.base == "hafan").unwrap();
+        assert_eq!(recovered.alternations, vec![Alternation::FinalNasalLoss]);
+        assert_eq!(recovered.number, Some(Number::Plural));
+
+        // "ka" follows "dosi" but not "ara".
+        let valid_word = generate_segmentations("dosika");
+        let split_word = valid_word.iter().find(|word| word.base == "dosi").unwrap();
+        assert_eq!(split_word.alternations, vec![Alternation::VelarPerfective]);
+        let valid_word = generate_segmentations("araka");
+        assert!(valid_word.iter().all(|word| word.base != "ara"));
+
+        let valid_word = generate_segmentations("arahabi");
+        let split_word = valid_word.iter().find(|word| word.base == "ara").unwrap();
+        assert!(split_word.alternations.is_empty());
+
+        // "eje" of "eje i" is "ejen" with its final n dropped before the clitic.
+        let valid_word = generate_segmentations_before_clitic("eje");
+        let recovered = valid_word.iter().find(|word| word.base == "ejen").unwrap();
+        assert_eq!(recovered.alternations, vec![Alternation::FinalNasalLoss]);
+    }
+
+    #[test]
+    fn test_normalized_token() {
+        // ū is precomposed in the suffixes and in the token alike.
+        let valid_word = generate_segmentations("genehekū");
+        let split_word = valid_word.iter().find(|word| word.base == "gene").unwrap();
+        assert_eq!(
+            split_word.detail,
+            Some(Detail::Conjugation(Conjugation::NegativePerfectiveConverb))
+        );
+
+        // A combining macron is normalized to the precomposed ū.
+        let valid_word = generate_segmentations("geneheku\u{304}");
+        assert!(valid_word.iter().any(|word| word.base == "gene"));
+        assert_eq!(valid_word[0].base, "genehekū");
+    }
+
+    #[test]
+    fn test_plural() {
+        let valid_word = generate_segmentations("hafasa");
+        let plural = valid_word.iter().find(|word| word.base == "hafa").unwrap();
+        assert_eq!(plural.number, Some(Number::Plural));
+        assert_eq!(valid_word[0].number, Some(Number::Singular));
+
+        // "se" is feminine and does not attach to "hafa".
+        let valid_word = generate_segmentations("hafase");
+        assert!(valid_word.iter().all(|word| word.base != "hafa"));
+    }
+
+    #[test]
+    fn test_features() {
+        let valid_word = generate_segmentations("tuwabunumbi");
+        let split_word = valid_word.iter().find(|word| word.base == "tuwa").unwrap();
+        let features = split_word.features();
+        assert_eq!(features.voice, Some(Voice::Cooperative));
+        assert_eq!(features.verb_form, Some(VerbForm::Finite));
+        assert_eq!(features.aspect, Some(Aspect::Imperfective));
+        assert_eq!(features.tense, Some(Tense::NonPast));
+        assert_eq!(
+            features.derivations,
+            vec![
+                Conjugation::PassiveCausativeVerbal,
+                Conjugation::CooperativeVerbal
+            ]
+        );
+
+        let valid_word = generate_segmentations("hafasa");
+        let plural = valid_word.iter().find(|word| word.base == "hafa").unwrap();
+        assert_eq!(plural.features().number, Some(Number::Plural));
+
+        let word = analyze_bare_imperative("tuwa", 0).unwrap();
+        assert_eq!(word.features().mood, Some(VerbMood::Imperative));
+        assert_eq!(word.features().sentence_mood, None);
+
+        let valid_word = generate_segmentations("genehe");
+        let perfective = valid_word.iter().find(|word| word.base == "gene").unwrap();
+        assert_eq!(perfective.features().tense, Some(Tense::Past));
+
+        let valid_word = generate_segmentations("genembio");
+        let question = valid_word.iter().find(|word| word.base == "gene").unwrap();
+        assert_eq!(question.features().sentence_mood, Some(Mood::Question));
+        assert_eq!(question.features().tense, Some(Tense::NonPast));
+    }
+
+    #[test]
+    fn test_imperative() {
+        let word = analyze_bare_imperative("tuwa", 0).unwrap();
+        assert_eq!(word.part_of_speech, PartOfSpeech::Verb);
+        assert_eq!(
+            word.detail,
+            Some(Detail::Conjugation(Conjugation::Imperative))
+        );
+        // "yabu" is not in the lexicon but is a verb if it looks more like a verb than a noun.
+        assert!(analyze_bare_imperative("yabu", -1).is_some());
+        assert!(analyze_bare_imperative("yabu", 0).is_none());
+        // "hafan" ends with a consonant however it looks.
+        assert!(analyze_bare_imperative("hafan", -1).is_none());
+        for token in ["niyalma", "minde", "cooha"] {
+            assert!(analyze_bare_imperative(token, 1).is_none());
+        }
+
+        let valid_word = generate_segmentations("tuwarao");
+        let polite = valid_word.iter().find(|word| word.base == "tuwa").unwrap();
+        assert_eq!(
+            polite.detail,
+            Some(Detail::Conjugation(Conjugation::PoliteImperative))
+        );
+        // "rao" is masculine and does not attach to "gene".
+        let valid_word = generate_segmentations("generao");
+        assert!(valid_word.iter().all(|word| word.base != "gene"));
+        let valid_word = generate_segmentations("genereo");
+        assert!(valid_word.iter().any(|word| word.base == "gene"));
     }
 }
@@ -0,0 +1,191 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// syllable in the table of the twelve heads (juwan juwe uju)
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Syllable {
+    /// romanized syllable
+    ///
+    /// For example, "a", "ba" or "bun".
+    pub syllable: String,
+    /// structure of the syllable
+    ///
+    /// For example, "V", "CV" or "CVC".
+    pub structure: String,
+    /// head (uju) which the syllable belongs to
+    ///
+    /// The first head consists of open syllables
+    /// and the others are grouped by the final, e.g. "-i", "-r" or "-n".
+    pub group: u8,
+    /// whether the syllable is special
+    ///
+    /// Special syllables are used in transcribing Chinese, e.g. "k'a" or "ts'i".
+    #[serde(rename = "isSpecial", deserialize_with = "deserialize_flag")]
+    pub is_special: bool,
+}
+
+fn deserialize_flag<'de, D>(deserializer: D) -> Result<bool, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let flag = u8::deserialize(deserializer)?;
+    Ok(flag != 0)
+}
+
+/// Split a romanized word into syllables.
+///
+/// If a word can be split in several ways, the one with the fewest syllables is chosen,
+/// and a consonant between vowels goes to the following syllable;
+/// e.g. "ama" is split into "a" and "ma".
+///
+/// Returns Err if the word is empty or contains a syllable absent from the table.
+///
+/// * `word` - A word to split.
+pub fn syllabify(word: &str) -> Result<Vec<Syllable>, String> {
+    if word.is_empty() {
+        return Err("Empty string".into());
+    }
+    let syllables = &*SYLLABLE_TABLE;
+
+    let boundaries = word
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(word.len()))
+        .collect::<Vec<usize>>();
+    // best[i] is the cost and the start of the last syllable of the best split of the first i characters.
+    let mut best: Vec<Option<(usize, usize)>> = vec![None; boundaries.len()];
+    best[0] = Some((0, 0));
+    for end in 1..boundaries.len() {
+        for start in 0..end {
+            let Some((cost, _)) = best[start] else {
+                continue;
+            };
+            let candidate = &word[boundaries[start]..boundaries[end]];
+            let Some(syllable) = syllables.get(candidate) else {
+                continue;
+            };
+            // Vowel-initial syllables are dispreferred except at the beginning of the word.
+            let onset_cost = if start > 0 && syllable.structure.starts_with('V') {
+                1
+            } else {
+                0
+            };
+            let cost = cost + 10 + onset_cost;
+            if best[end].is_none_or(|(best_cost, _)| cost < best_cost) {
+                best[end] = Some((cost, start));
+            }
+        }
+    }
+
+    let mut result = vec![];
+    let mut end = boundaries.len() - 1;
+    while end > 0 {
+        let Some((_, start)) = best[end] else {
+            return Err(format!("Cannot split \"{}\" into syllables", word));
+        };
+        let candidate = &word[boundaries[start]..boundaries[end]];
+        result.push(syllables[candidate].clone());
+        end = start;
+    }
+    result.reverse();
+    Ok(result)
+}
+
+/// Checks if a romanized word contains a special syllable,
+/// which means that the word is a loanword from Chinese.
+///
+/// Returns false if the word cannot be split into syllables.
+pub fn contains_special_syllable(word: &str) -> bool {
+    match syllabify(word) {
+        Ok(syllables) => syllables.iter().any(|syllable| syllable.is_special),
+        Err(_) => false,
+    }
+}
+
+/// syllables of the table indexed by the romanized syllable
+///
+/// The table is read once since `syllabify` is called for every candidate stem.
+static SYLLABLE_TABLE: LazyLock<HashMap<String, Syllable>> = LazyLock::new(|| {
+    read_syllable_csv()
+        .into_iter()
+        .map(|syllable| (syllable.syllable.clone(), syllable))
+        .collect()
+});
+
+fn read_syllable_csv() -> Vec<Syllable> {
+    let csv = include_str!("../resources/juwan_juwe_uju.csv");
+    let mut rdr = csv::Reader::from_reader(csv.as_bytes());
+    let mut syllables = Vec::new();
+    for result in rdr.deserialize() {
+        if let Ok(result) = result {
+            let syllable: Syllable = result;
+            syllables.push(syllable);
+        } else {
+            panic!("{}", result.unwrap_err());
+        }
+    }
+    syllables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_syllabify() {
+        let syllables = syllabify("tuwabumbi").unwrap();
+        let syllables = syllables
+            .iter()
+            .map(|syllable| syllable.syllable.as_str())
+            .collect::<Vec<&str>>();
+        assert_eq!(syllables, vec!["tu", "wa", "bum", "bi"]);
+
+        let syllables = syllabify("ama").unwrap();
+        assert_eq!(syllables[0].syllable, "a");
+        assert_eq!(syllables[0].structure, "V");
+        assert_eq!(syllables[1].syllable, "ma");
+        assert_eq!(syllables[1].group, 1);
+
+        let syllables = syllabify("cooha").unwrap();
+        assert_eq!(syllables[0].syllable, "coo");
+        assert_eq!(syllables[0].group, 5);
+
+        assert!(syllabify("").is_err());
+        assert!(syllabify("xyz").is_err());
+    }
+
+    #[test]
+    fn test_contains_special_syllable() {
+        assert!(contains_special_syllable("ts'ai"));
+        assert!(contains_special_syllable("dzungdu"));
+        assert!(!contains_special_syllable("cooha"));
+    }
+
+    #[test]
+    fn test_syllable_table_rows() {
+        // Every head of the juwan juwe uju after the first adds a fixed final.
+        const GROUP_FINALS: [&str; 11] = ["i", "n", "ng", "o", "r", "k", "t", "s", "b", "l", "m"];
+        let syllables = read_syllable_csv();
+        let mut seen = std::collections::HashSet::new();
+        for syllable in &syllables {
+            assert!(!syllable.syllable.is_empty());
+            assert!(syllable
+                .syllable
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || "ūšž'".contains(c)));
+            assert!(!syllable.structure.is_empty());
+            assert!(syllable.structure.chars().all(|c| c == 'C' || c == 'V'));
+            assert!((1..=12).contains(&syllable.group));
+            if syllable.group > 1 {
+                let last = GROUP_FINALS[usize::from(syllable.group) - 2];
+                assert!(syllable.syllable.ends_with(last), "{}", syllable.syllable);
+            }
+            assert!(
+                seen.insert(syllable.syllable.as_str()),
+                "{}",
+                syllable.syllable
+            );
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::LazyLock;
+
+use serde::Deserialize;
+
+use crate::{
+    phoneme::{ends_with_vowel, has_mixed_harmony},
+    split_suffix::analyze_bare_imperative,
+    syllable::syllabify,
+    word::{PartOfSpeech, Word},
+};
+
+/// character sequence suggesting the part of speech of a stem
+#[derive(Clone, Debug, Deserialize)]
+struct SegmentFeature {
+    segment: String,
+    part_of_speech: PartOfSpeech,
+    /// cost added to the stem if it has the segment
+    ///
+    /// A negative cost makes the part of speech more plausible.
+    cost: isize,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct UnknownWordFeatures {
+    /// features of the end of a stem, e.g. "bu" for a verb
+    final_segments: Vec<SegmentFeature>,
+    /// features of a character n-gram anywhere in a stem, e.g. "dz" for a noun
+    ngrams: Vec<SegmentFeature>,
+}
+
+/// cost added to a stem which cannot be split into syllables of the syllable table
+const INVALID_SYLLABLE_COST: isize = 3;
+/// cost added to a stem with both masculine and feminine vowels
+const MIXED_HARMONY_COST: isize = 2;
+/// cost added to a verb stem ending with a consonant
+const CONSONANT_FINAL_VERB_COST: isize = 5;
+
+/// guesser of the part of speech of a stem absent from the lexicons
+///
+/// The costs are small compared with the emission cost of a suffix (-5),
+/// so that they only decide between analyses with the same number of suffixes.
+/// They are set by hand in `unknown_word.json` and the constants above,
+/// not estimated from a corpus, so they should be re-tuned when an annotated corpus is available.
+pub struct UnknownWordModel {
+    features: UnknownWordFeatures,
+}
+
+/// model read once since it is consulted for every segmentation
+static UNKNOWN_WORD_MODEL: LazyLock<UnknownWordModel> = LazyLock::new(|| {
+    let data = include_str!("../resources/unknown_word.json");
+    let features: UnknownWordFeatures =
+        serde_json::from_str(data).expect("JSON was not well-formatted");
+    UnknownWordModel { features }
+});
+
+impl UnknownWordModel {
+    /// Returns the model read once from the resource.
+    pub fn load() -> &'static Self {
+        &UNKNOWN_WORD_MODEL
+    }
+
+    /// Returns the cost of `stem` being a stem of `part_of_speech`.
+    ///
+    /// The cost is the sum of the costs of the longest matching final segment,
+    /// the matching n-grams, the syllable structure and the vowel harmony.
+    pub fn stem_cost(&self, stem: &str, part_of_speech: PartOfSpeech) -> isize {
+        let final_segment_cost = self
+            .features
+            .final_segments
+            .iter()
+            .filter(|feature| {
+                feature.part_of_speech == part_of_speech && stem.ends_with(&feature.segment)
+            })
+            .max_by_key(|feature| feature.segment.chars().count())
+            .map_or(0, |feature| feature.cost);
+        let ngram_cost: isize = self
+            .features
+            .ngrams
+            .iter()
+            .filter(|feature| {
+                feature.part_of_speech == part_of_speech && stem.contains(&feature.segment)
+            })
+            .map(|feature| feature.cost)
+            .sum();
+
+        let mut cost = final_segment_cost + ngram_cost;
+        if syllabify(stem).is_err() {
+            cost += INVALID_SYLLABLE_COST;
+        }
+        if has_mixed_harmony(stem) {
+            cost += MIXED_HARMONY_COST;
+        }
+        if part_of_speech == PartOfSpeech::Verb && !ends_with_vowel(stem) {
+            cost += CONSONANT_FINAL_VERB_COST;
+        }
+        cost
+    }
+
+    /// Returns the probable parts of speech of an unsuffixed token with their costs.
+    ///
+    /// A noun is always returned as the fallback,
+    /// an adjective is returned only if it is more plausible than a noun,
+    /// and a verb is returned as the imperative only if the token looks like a verb stem.
+    pub fn guess(&self, token: &str) -> Vec<Word> {
+        let noun_cost = self.stem_cost(token, PartOfSpeech::Noun);
+        let adjective_cost = self.stem_cost(token, PartOfSpeech::Adjective);
+        let mut words = vec![bare_word(token, PartOfSpeech::Noun, noun_cost)];
+        if adjective_cost < noun_cost {
+            words.push(bare_word(token, PartOfSpeech::Adjective, adjective_cost));
+        }
+        let verb_noun_cost = self.stem_cost(token, PartOfSpeech::Verb) - noun_cost;
+        if let Some(mut imperative) = analyze_bare_imperative(token, verb_noun_cost) {
+            self.calibrate(&mut imperative);
+            words.push(imperative);
+        }
+        words
+    }
+
+    /// Adds the cost of the base of `word` to its emission cost.
+    pub fn calibrate(&self, word: &mut Word) {
+        word.emission_cost += self.stem_cost(&word.base, word.part_of_speech);
+    }
+}
+
+fn bare_word(token: &str, part_of_speech: PartOfSpeech, cost: isize) -> Word {
+    let mut word = Word::new(token.to_string(), None, part_of_speech, None);
+    word.emission_cost = cost;
+    word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stem_cost() {
+        let model = UnknownWordModel::load();
+        // "dosi" looks like a verb stem and "hafan" like a noun.
+        assert!(
+            model.stem_cost("dosi", PartOfSpeech::Verb)
+                < model.stem_cost("dosi", PartOfSpeech::Noun)
+        );
+        assert!(
+            model.stem_cost("hafan", PartOfSpeech::Noun)
+                < model.stem_cost("hafan", PartOfSpeech::Verb)
+        );
+        assert!(model.stem_cost("xyz", PartOfSpeech::Noun) > 0);
+    }
+
+    #[test]
+    fn test_guess() {
+        let model = UnknownWordModel::load();
+        let words = model.guess("sain");
+        assert_eq!(words.len(), 1);
+        assert_eq!(words[0].part_of_speech, PartOfSpeech::Noun);
+
+        let words = model.guess("sahaliyan");
+        assert_eq!(words[1].part_of_speech, PartOfSpeech::Adjective);
+        assert!(words[1].emission_cost < words[0].emission_cost);
+
+        // A bare verb stem is guessed as the imperative.
+        let words = model.guess("tuwa");
+        assert!(words
+            .iter()
+            .any(|word| word.part_of_speech == PartOfSpeech::Verb));
+        let words = model.guess("hafan");
+        assert!(words
+            .iter()
+            .all(|word| word.part_of_speech != PartOfSpeech::Verb));
+    }
+}
@@ -2,7 +2,9 @@ use manchu_converter::ManchuConverter;
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+use crate::phoneme::{ends_with_vowel, is_harmonic, Harmony};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum SuffixRole {
     Functional,
@@ -23,10 +25,15 @@ pub enum Conjugation {
     ImperfectiveConverb,
     NegativePerfectiveFinite,
     NegativePerfectiveConverb,
+    NegativeImperfectiveFinite,
     NegativeParticle,
     ProspectiveFinite,
     DesiderativeFinite,
     OptativeFinite,
+    /// imperative expressed by the bare stem, e.g. "tuwa"
+    Imperative,
+    /// polite imperative, e.g. "tuwarao" or "tuwacina"
+    PoliteImperative,
     DurativeConverb,
     ConditionalConverb,
     ConcessiveConverb,
@@ -36,11 +43,155 @@ pub enum Conjugation {
     SimultaneousConverb,
     AlternativeConverb,
     DenominalAdjective,
+    DeverbalNoun,
+    DeverbalAdjective,
+    Diminutive,
+    Comparative,
     PassiveCausativeVerbal,
+    CooperativeVerbal,
+    SimultaneousVerbal,
+    IterativeVerbal,
+    CentrifugalVerbal,
+    CentripetalVerbal,
+    DenominalVerbal,
     Plural,
     Interrogative,
 }
 
+impl Conjugation {
+    /// Checks if the conjugation ends a sentence or a clause.
+    pub fn is_finite(&self) -> bool {
+        matches!(
+            self,
+            Conjugation::PerfectiveFinite
+                | Conjugation::ImperfectiveFinite
+                | Conjugation::NegativePerfectiveFinite
+                | Conjugation::NegativeImperfectiveFinite
+                | Conjugation::ProspectiveFinite
+                | Conjugation::DesiderativeFinite
+                | Conjugation::OptativeFinite
+                | Conjugation::Imperative
+                | Conjugation::PoliteImperative
+        )
+    }
+
+    /// Returns the inflectional features which the conjugation marks.
+    ///
+    /// Derivations are added by `Suffix::features` since they depend on the role.
+    pub fn features(&self) -> Features {
+        use Conjugation::*;
+        let (verb_form, aspect, polarity, mood) = match self {
+            PerfectiveFinite => (Some(VerbForm::Finite), Some(Aspect::Perfective), None, None),
+            PerfectiveConverb => (
+                Some(VerbForm::Converb),
+                Some(Aspect::Perfective),
+                None,
+                None,
+            ),
+            PerfectiveParticiple => (
+                Some(VerbForm::Participle),
+                Some(Aspect::Perfective),
+                None,
+                None,
+            ),
+            PerfectiveProcessiveParticiple => (
+                Some(VerbForm::Participle),
+                Some(Aspect::Imperfective),
+                None,
+                None,
+            ),
+            ImperfectiveFinite | ProspectiveFinite => (
+                Some(VerbForm::Finite),
+                Some(Aspect::Imperfective),
+                None,
+                None,
+            ),
+            ImperfectiveConverb => (
+                Some(VerbForm::Converb),
+                Some(Aspect::Imperfective),
+                None,
+                None,
+            ),
+            NegativePerfectiveFinite => (
+                Some(VerbForm::Finite),
+                Some(Aspect::Perfective),
+                Some(Polarity::Negative),
+                None,
+            ),
+            NegativePerfectiveConverb => (
+                Some(VerbForm::Converb),
+                Some(Aspect::Perfective),
+                Some(Polarity::Negative),
+                None,
+            ),
+            NegativeImperfectiveFinite => (
+                Some(VerbForm::Finite),
+                Some(Aspect::Imperfective),
+                Some(Polarity::Negative),
+                None,
+            ),
+            NegativeParticle => (
+                Some(VerbForm::Participle),
+                None,
+                Some(Polarity::Negative),
+                None,
+            ),
+            DesiderativeFinite => (
+                Some(VerbForm::Finite),
+                None,
+                None,
+                Some(VerbMood::Desiderative),
+            ),
+            OptativeFinite => (Some(VerbForm::Finite), None, None, Some(VerbMood::Optative)),
+            Imperative | PoliteImperative => (
+                Some(VerbForm::Finite),
+                None,
+                None,
+                Some(VerbMood::Imperative),
+            ),
+            DurativeConverb | ConditionalConverb | ConcessiveConverb | TerminativeConverb
+            | PrefactoryConverb | ApprehensiveConverb | SimultaneousConverb
+            | AlternativeConverb => (Some(VerbForm::Converb), None, None, None),
+            _ => (None, None, None, None),
+        };
+        // The perfective forms and the participles in "-mbihe" refer to the past.
+        let tense = match self {
+            PerfectiveFinite
+            | PerfectiveParticiple
+            | PerfectiveProcessiveParticiple
+            | NegativePerfectiveFinite => Some(Tense::Past),
+            ImperfectiveFinite | ProspectiveFinite | NegativeImperfectiveFinite => {
+                Some(Tense::NonPast)
+            }
+            _ => None,
+        };
+        let sentence_mood = match self {
+            Interrogative => Some(Mood::Question),
+            _ => None,
+        };
+        let voice = match self {
+            PassiveCausativeVerbal => Some(Voice::PassiveCausative),
+            CooperativeVerbal => Some(Voice::Cooperative),
+            _ => None,
+        };
+        let number = match self {
+            Plural => Some(Number::Plural),
+            _ => None,
+        };
+        Features {
+            voice,
+            aspect,
+            tense,
+            polarity,
+            mood,
+            sentence_mood,
+            verb_form,
+            number,
+            derivations: vec![],
+        }
+    }
+}
+
 #[derive(Clone, Debug, Display, EnumString, PartialEq, Serialize, Deserialize)]
 #[strum(serialize_all = "snake_case")]
 pub enum Case {
@@ -48,6 +199,8 @@ pub enum Case {
     Accusative,
     Genitive,
     DativeLocative,
+    Ablative,
+    Prolative,
     Instrumental,
     Vocative,
 }
@@ -60,20 +213,215 @@ pub enum Detail {
 }
 
 /// part of speech which suffix attaches to
-#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, Display, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 #[strum(serialize_all = "snake_case")]
 pub enum PartOfSpeech {
     Noun,
+    /// personal, clan, place or banner name, e.g. "Nurgaci"
+    ProperNoun,
+    Adjective,
     Verb,
     Clitic,
     Postposition,
     Auxiliary,
     Conjunction,
     Particle,
+    Pronoun,
+    /// quotative use of the verb "se", e.g. "seme" or "sehe"
+    Quotative,
+    /// sound-symbolic word, e.g. "kiyatar" or "pak"
+    Mimetic,
+    /// e.g. reduplicated forms such as "jai jai"
+    Adverb,
+    #[default]
     Unknown,
 }
 
+/// grammatical number of a noun
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Number {
+    Singular,
+    Plural,
+}
+
+/// grammatical person of a pronoun
+///
+/// Demonstratives such as "ere" and "tere" are third person.
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Person {
+    First,
+    Second,
+    Third,
+}
+
+/// polarity of a verb or a verbal construction
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Polarity {
+    Positive,
+    Negative,
+}
+
+/// aspect of a verb or a verbal construction
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Aspect {
+    Perfective,
+    Imperfective,
+    /// ongoing action, e.g. "tuwame bi"
+    Progressive,
+}
+
+/// tense of a verb or a verbal construction
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Tense {
+    Past,
+    NonPast,
+}
+
+/// mood of a sentence marked by a sentence-final particle or an interrogative suffix
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Mood {
+    /// e.g. "o", "ni" or "na"
+    Question,
+    /// e.g. "kai" or "dabala"
+    Assertion,
+    /// e.g. "dere" or "aise"
+    Conjecture,
+}
+
+/// mood of a verb marked by its conjugation
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum VerbMood {
+    /// e.g. "tuwa" or "tuwacina"
+    Imperative,
+    /// e.g. "tuwaki"
+    Desiderative,
+    /// e.g. "tuwakini"
+    Optative,
+}
+
+/// voice of a verb marked by a derivational suffix
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Voice {
+    /// e.g. "tuwabumbi"
+    PassiveCausative,
+    /// e.g. "tuwanumbi"
+    Cooperative,
+}
+
+/// non-derivational form of a verb
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum VerbForm {
+    /// e.g. "tuwambi" or "tuwaha"
+    Finite,
+    /// e.g. "tuwaha" modifying a noun
+    Participle,
+    /// e.g. "tuwame" or "tuwafi"
+    Converb,
+}
+
+/// morphological features marked by suffixes
+///
+/// A feature is None if no suffix marks it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Features {
+    pub voice: Option<Voice>,
+    pub aspect: Option<Aspect>,
+    pub tense: Option<Tense>,
+    pub polarity: Option<Polarity>,
+    /// mood of the verb, e.g. the imperative
+    pub mood: Option<VerbMood>,
+    /// mood of the sentence marked by the word, e.g. a question
+    pub sentence_mood: Option<Mood>,
+    pub verb_form: Option<VerbForm>,
+    pub number: Option<Number>,
+    /// derivations of the word from the innermost one
+    ///
+    /// For example, the derivations of "tuwabunumbi" are
+    /// `vec![PassiveCausativeVerbal, CooperativeVerbal]`.
+    pub derivations: Vec<Conjugation>,
+}
+
+impl Features {
+    /// Merge the features of a suffix attached outside of the suffixes of `self`.
+    ///
+    /// The features of the outer suffix override the inner ones
+    /// and the derivations are accumulated.
+    pub fn merge(&mut self, outer: Features) {
+        self.voice = outer.voice.or(self.voice);
+        self.aspect = outer.aspect.or(self.aspect);
+        self.tense = outer.tense.or(self.tense);
+        self.polarity = outer.polarity.or(self.polarity);
+        self.mood = outer.mood.or(self.mood);
+        self.sentence_mood = outer.sentence_mood.or(self.sentence_mood);
+        self.verb_form = outer.verb_form.or(self.verb_form);
+        self.number = outer.number.or(self.number);
+        self.derivations.extend(outer.derivations);
+    }
+}
+
+/// origin of a word
+#[derive(Clone, Copy, Debug, Default, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Origin {
+    #[default]
+    Native,
+    /// loanword from Chinese
+    ///
+    /// It is detected by special syllables such as "k'a" or "ts'i".
+    Chinese,
+}
+
+/// alternation at a morpheme boundary
+#[derive(Clone, Copy, Debug, Display, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Alternation {
+    /// The final n of a noun is dropped before a suffix or a clitic.
+    ///
+    /// For example, "hafasa" is the plural of "hafan".
+    FinalNasalLoss,
+    /// The perfective converb "fi" surfaces as "mpi" or "pi".
+    LabialConverb,
+    /// The perfective "ha", "he" and "ho" surface as "ka", "ke" and "ko"
+    /// after some stems, e.g. "dosika" or "jeke".
+    VelarPerfective,
+}
+
+/// constraint on the end of the base which a suffix attaches to
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StemConstraint {
+    Any,
+    /// The base must end with a vowel.
+    ///
+    /// For example, the plural suffix "sa" attaches to "hafa" of "hafan".
+    ///
+    /// The plural suffixes "ta" and "te" attach only to human nouns such as "ama" or "eme",
+    /// but the constraint is not modelled because there is no lexicon of noun classes;
+    /// they are only restricted to vowel-final bases like the other plural suffixes.
+    VowelFinal,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Suffix {
     /// suffix
@@ -91,9 +439,54 @@ pub struct Suffix {
     /// part of speech of the word which the suffix attaches to
     #[serde(rename = "left_pos")]
     pub part_of_speech: PartOfSpeech,
+    /// part of speech of the word which the suffix makes
+    ///
+    /// For example, "cun" attaches to a verb and makes a noun.
+    /// Functional suffixes keep the part of speech of the word which they attach to.
+    #[serde(rename = "right_pos")]
+    pub right_part_of_speech: PartOfSpeech,
+    /// vowel harmony class of suffix
+    ///
+    /// For example, "ha" is masculine, "he" is feminine and "ho" is rounded.
+    /// The harmony applies to verbal suffixes as well as nominal ones,
+    /// so a feminine suffix does not attach to a masculine base.
+    pub harmony: Harmony,
+    /// constraint on the end of the base
+    #[serde(rename = "stem")]
+    pub stem_constraint: StemConstraint,
+    /// emission cost of suffix
+    ///
+    /// Basically, the cost is -5, but an ambiguous suffix such as deverbal "n" or "kū"
+    /// costs more than the unsuffixed word, so that it does not beat a lexical entry
+    /// such as "tumen" or "akū".
+    pub cost: isize,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl Suffix {
+    /// Checks if the suffix can attach to `base`
+    /// according to the vowel harmony and the stem constraint.
+    pub fn can_attach_to(&self, base: &str) -> bool {
+        let satisfies_stem_constraint = match self.stem_constraint {
+            StemConstraint::Any => true,
+            StemConstraint::VowelFinal => ends_with_vowel(base),
+        };
+        satisfies_stem_constraint && is_harmonic(base, self.harmony)
+    }
+
+    /// Returns the features which the suffix marks.
+    ///
+    /// A suffix which is not functional also records its derivation,
+    /// e.g. "bu" in "tuwabumbi".
+    pub fn features(&self) -> Features {
+        let mut features = self.conjugation.features();
+        if self.role != SuffixRole::Functional {
+            features.derivations.push(self.conjugation);
+        }
+        features
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Word {
     /// base of the word
     ///
@@ -112,8 +505,23 @@ pub struct Word {
     pub detail: Option<Detail>,
     /// emission cost of the word
     ///
-    /// Basically, the emission cost is the negative of the number of suffixes.
+    /// Basically, the emission cost is the sum of the costs of the suffixes.
     pub emission_cost: isize,
+    /// number of the word
+    ///
+    /// Only nouns and pronouns have a number;
+    /// a noun is plural if the word has a plural suffix.
+    pub number: Option<Number>,
+    /// person of the word
+    ///
+    /// Only pronouns have a person.
+    pub person: Option<Person>,
+    /// origin of the word
+    pub origin: Origin,
+    /// alternations at the morpheme boundaries of the word
+    ///
+    /// If the final n of the base has been recovered, the base includes it.
+    pub alternations: Vec<Alternation>,
 }
 
 impl Word {
@@ -123,12 +531,94 @@ impl Word {
         part_of_speech: PartOfSpeech,
         detail: Option<Detail>,
     ) -> Self {
+        let derived_part_of_speech = suffixes
+            .as_ref()
+            .and_then(|suffixes| suffixes.first())
+            .map_or(part_of_speech, |suffix| suffix.right_part_of_speech);
+        let number = match derived_part_of_speech {
+            PartOfSpeech::Noun => {
+                let is_plural = suffixes
+                    .iter()
+                    .flatten()
+                    .any(|suffix| suffix.conjugation == Conjugation::Plural);
+                if is_plural {
+                    Some(Number::Plural)
+                } else {
+                    Some(Number::Singular)
+                }
+            }
+            _ => None,
+        };
         Self {
             base,
             suffixes: suffixes.clone(),
             part_of_speech,
             detail,
-            emission_cost: -5 * suffixes.clone().unwrap_or(vec![]).len() as isize,
+            emission_cost: suffixes.iter().flatten().map(|suffix| suffix.cost).sum(),
+            number,
+            person: None,
+            origin: Origin::Native,
+            alternations: vec![],
+        }
+    }
+
+    /// Returns the mood which the word marks.
+    ///
+    /// A word marks a mood if it is a sentence particle
+    /// or it has an interrogative suffix such as "o".
+    pub fn mood(&self) -> Option<Mood> {
+        match &self.detail {
+            Some(Detail::Conjugation(Conjugation::Interrogative)) => Some(Mood::Question),
+            Some(Detail::Other(detail)) if self.part_of_speech == PartOfSpeech::Particle => {
+                match detail.as_str() {
+                    "interrogative" => Some(Mood::Question),
+                    "assertive" => Some(Mood::Assertion),
+                    "conjectural" => Some(Mood::Conjecture),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the morphological features of the whole word.
+    ///
+    /// The features of the suffixes are merged from the innermost suffix,
+    /// so that the outer suffixes override the inner ones.
+    /// A word without suffixes, e.g. an irregular form, has the features of its detail.
+    pub fn features(&self) -> Features {
+        let mut features = match (&self.suffixes, &self.detail) {
+            (Some(suffixes), _) => {
+                suffixes
+                    .iter()
+                    .rev()
+                    .fold(Features::default(), |mut features, suffix| {
+                        features.merge(suffix.features());
+                        features
+                    })
+            }
+            (None, Some(Detail::Conjugation(conjugation))) => conjugation.features(),
+            (None, _) => Features::default(),
+        };
+        features.number = self.number.or(features.number);
+        features.sentence_mood = self.mood().or(features.sentence_mood);
+        features
+    }
+
+    /// Checks if the word is a loanword.
+    pub fn is_loanword(&self) -> bool {
+        self.origin != Origin::Native
+    }
+
+    /// Returns the part of speech of the whole word.
+    ///
+    /// The field `part_of_speech` is the part of speech of the base,
+    /// and derivational suffixes may change it.
+    /// For example, "ulhicun" is a noun derived from the verb base "ulhi".
+    pub fn derived_part_of_speech(&self) -> PartOfSpeech {
+        match self.suffixes.as_ref().and_then(|suffixes| suffixes.first()) {
+            Some(suffix) => suffix.right_part_of_speech,
+            None => self.part_of_speech,
         }
     }
 
@@ -138,28 +628,18 @@ impl Word {
             .clone()
             .convert_to_manchu()
             .expect("cannot convert to manchu");
-        let suffixes: Option<Vec<Suffix>>;
-        match self.suffixes {
-            Some(ref s) => {
-                suffixes = Some(
-                    s.iter()
-                        .map(|suffix| Suffix {
-                            suffix: suffix
-                                .suffix
-                                .clone()
-                                .convert_to_manchu()
-                                .expect("cannot convert to manchu"),
-                            conjugation: suffix.conjugation,
-                            role: suffix.role,
-                            part_of_speech: suffix.part_of_speech,
-                        })
-                        .collect(),
-                );
-            }
-            None => {
-                suffixes = None;
-            }
-        };
+        let suffixes: Option<Vec<Suffix>> = self.suffixes.as_ref().map(|s| {
+            s.iter()
+                .map(|suffix| Suffix {
+                    suffix: suffix
+                        .suffix
+                        .clone()
+                        .convert_to_manchu()
+                        .expect("cannot convert to manchu"),
+                    ..suffix.clone()
+                })
+                .collect()
+        });
         self.base = base;
         self.suffixes = suffixes;
     }